@@ -69,5 +69,7 @@ fn message_destination_string(destination: &MessageDestination<Shared<Node>>) ->
             .map(|node| node.borrow().name.clone())
             .collect::<Vec<_>>()
             .join(", "),
+        MessageDestination::<Shared<Node>>::Multicast(group_id) => format!("group {}", group_id),
+        MessageDestination::<Shared<Node>>::Anycast(group_id) => format!("any member of group {}", group_id),
     }
 }
@@ -26,7 +26,7 @@ pub trait BinarySerde: Serialize + DeserializeOwned {
     }
 
     fn from_data(data: &Data) -> Self {
-        bincode::deserialize(&*data).unwrap()
+        Self::try_from_data(data).unwrap()
         // let mut hasher = DefaultHasher::new();
         // data.hash(&mut hasher);
         // let hash = hasher.finish();
@@ -38,6 +38,10 @@ pub trait BinarySerde: Serialize + DeserializeOwned {
         //     .or_insert_with(|| Box::new(bincode::deserialize(&*data).unwrap()))
         //     .clone()
     }
+
+    fn try_from_data(data: &[u8]) -> bincode::Result<Self> {
+        bincode::deserialize(data)
+    }
 }
 
 // lazy_static! {
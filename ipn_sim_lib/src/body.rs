@@ -1,4 +1,6 @@
-use cgmath::Point3;
+use std::f64::consts::PI;
+
+use cgmath::{InnerSpace, Point3, Quaternion, Rad, Rotation, Rotation3, Vector3, Zero};
 use serde::Serialize;
 use typescript_definitions::TypescriptDefinition;
 use wasm_bindgen::prelude::*;
@@ -13,11 +15,149 @@ pub struct Body {
     pub position: Point3<SpaceMetric>,
     #[serde(skip)]
     pub movement: Box<dyn Movement>,
+    /// Equatorial radius.
     pub radius: SpaceMetric,
+    #[serde(skip)]
+    pub rotation: BodyRotation,
+    /// Polar flattening, zero for a sphere.
+    #[serde(skip)]
+    pub flattening: SpaceMetric,
+    /// Height above the surface within which passing links are rejected, e.g. for an atmosphere.
+    #[serde(skip)]
+    pub grazing_margin: SpaceMetric,
 }
 
 impl Body {
     pub fn set_position(&mut self, time: TimeMetric) {
         self.position = self.movement.get_position_at(time);
     }
+
+    pub fn polar_radius(&self) -> SpaceMetric {
+        self.radius * (1. - self.flattening)
+    }
+
+    /// Distance from the centre to the surface in the direction of `latitude`, measured from the
+    /// centre rather than along the surface normal.
+    pub fn radius_at(&self, latitude: SpaceMetric) -> SpaceMetric {
+        let (equatorial, polar) = (self.radius, self.polar_radius());
+        1. / ((latitude.cos() / equatorial).powi(2) + (latitude.sin() / polar).powi(2)).sqrt()
+    }
+
+    /// Whether the straight line between two points passes through the body or within its grazing
+    /// margin, or either point lies inside them.
+    pub fn occludes(&self, from: Point3<SpaceMetric>, to: Point3<SpaceMetric>) -> bool {
+        self.occludes_between(from, false, to, false)
+    }
+
+    /// Like `occludes`, with ends flagged as standing on the surface exempt from being inside the
+    /// body or its margin. Lines are only blocked where they pass between their ends, so a node on
+    /// the surface sees everything above its horizon.
+    pub fn occludes_between(
+        &self,
+        from: Point3<SpaceMetric>,
+        from_on_surface: bool,
+        to: Point3<SpaceMetric>,
+        to_on_surface: bool,
+    ) -> bool {
+        // Scale the body's polar axis so the limb, with its margin, becomes a sphere.
+        let equatorial = self.radius + self.grazing_margin;
+        let polar = self.polar_radius() + self.grazing_margin;
+        let pole = self.rotation.polar_axis();
+        let to_sphere = |point: Point3<SpaceMetric>| {
+            let offset = point - self.position;
+            offset + pole * (offset.dot(pole) * (equatorial / polar - 1.))
+        };
+        let (from, to) = (to_sphere(from), to_sphere(to));
+
+        let inside = |point: Vector3<SpaceMetric>, on_surface: bool| !on_surface && point.magnitude2() < equatorial.powi(2);
+        if inside(from, from_on_surface) || inside(to, to_on_surface) {
+            return true;
+        }
+
+        let direction = to - from;
+        let length2 = direction.magnitude2();
+        if length2 == 0. {
+            return false;
+        }
+        let closest = -from.dot(direction) / length2;
+        closest > 0. && closest < 1. && (from + direction * closest).magnitude2() < equatorial.powi(2)
+    }
+}
+
+/// Spin of a body about its pole, which is the y axis tilted about the x axis by `axial_tilt`.
+/// `period` is the sidereal rotation period in nanoseconds, negative for retrograde rotation, and
+/// zero for a body that does not rotate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BodyRotation {
+    pub period: SpaceMetric,
+    pub axial_tilt: SpaceMetric,
+    /// Angle of the prime meridian from the x axis at time zero.
+    pub meridian_at_epoch: SpaceMetric,
+}
+
+impl BodyRotation {
+    pub fn new(period: SpaceMetric, axial_tilt: SpaceMetric, meridian_at_epoch: SpaceMetric) -> Self {
+        Self {
+            period,
+            axial_tilt,
+            meridian_at_epoch,
+        }
+    }
+
+    pub fn meridian_at(&self, time: TimeMetric) -> SpaceMetric {
+        if self.period == 0. {
+            return self.meridian_at_epoch;
+        }
+        self.meridian_at_epoch + (time as SpaceMetric / self.period % 1.) * 2. * PI
+    }
+
+    /// Unit vector towards the north pole in the sim frame.
+    pub fn polar_axis(&self) -> Vector3<SpaceMetric> {
+        Quaternion::from_angle_x(Rad(self.axial_tilt)).rotate_vector(Vector3::unit_y())
+    }
+
+    /// Angular velocity in radians per second.
+    pub fn angular_velocity(&self) -> Vector3<SpaceMetric> {
+        if self.period == 0. {
+            return Vector3::zero();
+        }
+        self.polar_axis() * (2. * PI / (self.period / 1e9))
+    }
+
+    /// Rotation from the body fixed frame, y towards the north pole and x towards the prime
+    /// meridian, to the sim frame.
+    pub fn orientation_at(&self, time: TimeMetric) -> Quaternion<SpaceMetric> {
+        Quaternion::from_angle_x(Rad(self.axial_tilt)) * Quaternion::from_angle_y(Rad(self.meridian_at(time)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{EuclideanSpace, Point3};
+
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::movements::static_movement::StaticMovement;
+
+    #[test]
+    fn test_oblate_body_occlusion() {
+        let builder = IpnSimBuilder::new(0)
+            .add_body("Planet", 1e24, StaticMovement::new(Point3::origin()), 1e6)
+            .oblate_body("Planet", 0.3);
+        let body = builder.get_body(0);
+
+        // Passing over the pole clears the flattened body but not the equatorial bulge.
+        assert!(!body.borrow().occludes(Point3::new(-2e6, 0.8e6, 0.), Point3::new(2e6, 0.8e6, 0.)));
+        assert!(body.borrow().occludes(Point3::new(-2e6, 0., 0.8e6), Point3::new(2e6, 0., 0.8e6)));
+        assert!((body.borrow().radius_at(std::f64::consts::FRAC_PI_2) - 0.7e6).abs() < 1e-6);
+        // A node on the surface sees straight up.
+        assert!(!body.borrow().occludes_between(Point3::new(1e6, 0., 0.), true, Point3::new(2e6, 0., 0.), false));
+
+        let body = builder.body_grazing_margin("Planet", 0.2e6).get_body(0);
+        assert!(body.borrow().occludes(Point3::new(-2e6, 0.8e6, 0.), Point3::new(2e6, 0.8e6, 0.)));
+        // Surface nodes are within the margin but still see up, a satellite that has decayed into
+        // the margin or below the surface does not.
+        assert!(!body.borrow().occludes_between(Point3::new(1e6, 0., 0.), true, Point3::new(2e6, 0., 0.), false));
+        assert!(body.borrow().occludes(Point3::new(1.1e6, 0., 0.), Point3::new(2e6, 0., 0.)));
+        assert!(body.borrow().occludes(Point3::new(0.9e6, 0., 0.), Point3::new(2e6, 0., 0.)));
+    }
 }
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::bundle::endpoint_id::EndpointId;
+use crate::message_destination::MessageDestination;
+use crate::utils::{Data, TimeMetric};
+use crate::wire_size::{WireSize, TAG_SIZE};
+
+/// Creation time of a bundle at its source, disambiguated by a per-source sequence number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CreationTimestamp {
+    pub time: TimeMetric,
+    pub sequence_number: u16,
+}
+
+/// Position of a fragment's payload within the payload of the original bundle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FragmentOffset {
+    pub offset: u32,
+    pub total_length: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PrimaryBlock {
+    pub destination: MessageDestination<EndpointId>,
+    pub source: EndpointId,
+    pub report_to: EndpointId,
+    pub creation_timestamp: CreationTimestamp,
+    pub lifetime: TimeMetric,
+    pub fragment_offset: Option<FragmentOffset>,
+    /// Whether the payload is an administrative record, e.g. a status report.
+    pub is_admin_record: bool,
+    pub status_reports_requested: bool,
+    pub custody_transfer_requested: bool,
+    pub must_not_fragment: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum ExtensionBlock {
+    PreviousNode(EndpointId),
+    HopCount {
+        limit: u8,
+        count: u8,
+    },
+    BundleAge(TimeMetric),
+    /// The node currently holding custody of the bundle.
+    Custodian(EndpointId),
+    Other {
+        block_type: u8,
+        data: Data,
+    },
+}
+
+impl WireSize for CreationTimestamp {
+    fn wire_size(&self) -> usize {
+        self.time.wire_size() + self.sequence_number.wire_size()
+    }
+}
+
+impl WireSize for FragmentOffset {
+    fn wire_size(&self) -> usize {
+        self.offset.wire_size() + self.total_length.wire_size()
+    }
+}
+
+impl WireSize for PrimaryBlock {
+    fn wire_size(&self) -> usize {
+        // Version and processing control flags.
+        2
+            + self.destination.wire_size()
+            + self.source.wire_size()
+            + self.report_to.wire_size()
+            + self.creation_timestamp.wire_size()
+            + self.lifetime.wire_size()
+            + self.fragment_offset.wire_size()
+    }
+}
+
+impl WireSize for ExtensionBlock {
+    fn wire_size(&self) -> usize {
+        TAG_SIZE
+            + match self {
+                ExtensionBlock::PreviousNode(endpoint_id) => endpoint_id.wire_size(),
+                ExtensionBlock::HopCount { limit, count } => limit.wire_size() + count.wire_size(),
+                ExtensionBlock::BundleAge(age) => age.wire_size(),
+                ExtensionBlock::Custodian(endpoint_id) => endpoint_id.wire_size(),
+                ExtensionBlock::Other { block_type, data } => block_type.wire_size() + data.wire_size(),
+            }
+    }
+}
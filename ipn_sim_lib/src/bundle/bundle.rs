@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+use crate::binary_serde::BinarySerde;
+use crate::bundle::block::{CreationTimestamp, ExtensionBlock, FragmentOffset, PrimaryBlock};
+use crate::bundle::endpoint_id::EndpointId;
+use crate::ipn_sim::groups::Groups;
+use crate::message_destination::{IsIncluded, MessageDestination};
+use crate::utils;
+use crate::utils::{Data, MessageId, NodeId, TimeMetric};
+use crate::wire_size::{WireSize, LENGTH_PREFIX_SIZE};
+
+pub const DEFAULT_HOP_LIMIT: u8 = 32;
+
+/// Identifies a bundle, or a fragment of one, across the network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BundleId {
+    pub source: EndpointId,
+    pub creation_timestamp: CreationTimestamp,
+    pub fragment_offset: Option<FragmentOffset>,
+}
+
+impl BundleId {
+    /// The simulation message id of the bundle, shared by all of its fragments.
+    pub fn message_id(&self) -> MessageId {
+        utils::message_id(
+            self.source.node_id().unwrap_or(0),
+            self.creation_timestamp.sequence_number,
+        )
+    }
+}
+
+/// A Bundle Protocol version 7 style bundle: a primary block, extension blocks and a payload.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Bundle {
+    pub primary: PrimaryBlock,
+    pub extensions: Vec<ExtensionBlock>,
+    pub payload: Data,
+}
+
+impl Bundle {
+    pub fn new(
+        source_id: NodeId,
+        sequence_number: u16,
+        destination: MessageDestination<NodeId>,
+        payload: Data,
+        creation_time: TimeMetric,
+        lifetime: TimeMetric,
+    ) -> Self {
+        Self {
+            primary: PrimaryBlock {
+                destination: destination.into(),
+                source: EndpointId::ipn(source_id),
+                report_to: EndpointId::ipn(source_id),
+                creation_timestamp: CreationTimestamp {
+                    time: creation_time,
+                    sequence_number,
+                },
+                lifetime,
+                fragment_offset: None,
+                is_admin_record: false,
+                status_reports_requested: false,
+                custody_transfer_requested: false,
+                must_not_fragment: false,
+            },
+            extensions: vec![
+                ExtensionBlock::HopCount {
+                    limit: DEFAULT_HOP_LIMIT,
+                    count: 0,
+                },
+            ],
+            payload,
+        }
+    }
+
+    pub fn id(&self) -> BundleId {
+        BundleId {
+            source: self.primary.source,
+            creation_timestamp: self.primary.creation_timestamp,
+            fragment_offset: self.primary.fragment_offset,
+        }
+    }
+
+    pub fn message_id(&self) -> MessageId {
+        self.id().message_id()
+    }
+
+    pub fn source_id(&self) -> Option<NodeId> {
+        self.primary.source.node_id()
+    }
+
+    pub fn is_destined_to(&self, node_id: NodeId, groups: &Groups) -> bool {
+        self.primary.destination.is_included(&node_id, groups)
+    }
+
+    pub fn expiry_time(&self) -> TimeMetric {
+        self.primary.creation_timestamp.time + self.primary.lifetime
+    }
+
+    pub fn is_expired(&self, time: TimeMetric) -> bool {
+        self.expiry_time() <= time
+    }
+
+    pub fn previous_node(&self) -> Option<EndpointId> {
+        self.extensions.iter().find_map(|block| match block {
+            ExtensionBlock::PreviousNode(endpoint_id) => Some(*endpoint_id),
+            _ => None,
+        })
+    }
+
+    pub fn hop_count(&self) -> Option<(u8, u8)> {
+        self.extensions.iter().find_map(|block| match block {
+            ExtensionBlock::HopCount { limit, count } => Some((*limit, *count)),
+            _ => None,
+        })
+    }
+
+    pub fn custodian(&self) -> Option<EndpointId> {
+        self.extensions.iter().find_map(|block| match block {
+            ExtensionBlock::Custodian(endpoint_id) => Some(*endpoint_id),
+            _ => None,
+        })
+    }
+
+    pub fn set_custodian(&mut self, node_id: NodeId) {
+        self.extensions.retain(|block| !matches!(block, ExtensionBlock::Custodian(_)));
+        self.extensions.push(ExtensionBlock::Custodian(EndpointId::ipn(node_id)));
+    }
+
+    /// Stamps the bundle as leaving `node_id`, returning false if it has exhausted its hop limit.
+    pub fn prepare_forward(&mut self, node_id: NodeId) -> bool {
+        let mut within_limit = true;
+        let mut has_previous_node = false;
+        for block in &mut self.extensions {
+            match block {
+                ExtensionBlock::PreviousNode(endpoint_id) => {
+                    *endpoint_id = EndpointId::ipn(node_id);
+                    has_previous_node = true;
+                }
+                ExtensionBlock::HopCount { limit, count } => {
+                    *count = count.saturating_add(1);
+                    within_limit = *count <= *limit;
+                }
+                _ => {}
+            }
+        }
+        if !has_previous_node {
+            self.extensions.push(ExtensionBlock::PreviousNode(EndpointId::ipn(node_id)));
+        }
+        within_limit
+    }
+
+    pub fn header_size(&self) -> usize {
+        self.primary.wire_size() + self.extensions.wire_size() + LENGTH_PREFIX_SIZE
+    }
+}
+
+impl WireSize for Bundle {
+    fn wire_size(&self) -> usize {
+        self.header_size() + self.payload.len()
+    }
+}
+
+impl BinarySerde for Bundle {}
+
+#[cfg(test)]
+mod tests {
+    use crate::bundle::block::ExtensionBlock;
+    use crate::bundle::bundle::Bundle;
+    use crate::bundle::endpoint_id::EndpointId;
+    use crate::ipn_sim::groups::Groups;
+    use crate::message_destination::MessageDestination;
+
+    #[test]
+    fn test_prepare_forward() {
+        let mut bundle = Bundle::new(1, 7, MessageDestination::Single(2), vec![0; 8].into_boxed_slice(), 0, 100);
+        bundle.extensions[0] = ExtensionBlock::HopCount { limit: 1, count: 0 };
+
+        assert_eq!(bundle.message_id(), 1 << 16 | 7);
+        assert!(bundle.is_destined_to(2, &Groups::default()));
+        assert!(bundle.prepare_forward(3));
+        assert_eq!(bundle.previous_node(), Some(EndpointId::ipn(3)));
+        assert!(!bundle.prepare_forward(4));
+        assert_eq!(bundle.previous_node(), Some(EndpointId::ipn(4)));
+        assert!(bundle.is_expired(100));
+    }
+}
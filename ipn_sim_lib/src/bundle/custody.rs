@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::bundle::bundle::BundleId;
+use crate::utils::NodeId;
+use crate::wire_size::{WireSize, TAG_SIZE};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CustodyRefusal {
+    /// The bundle was already received, so the sender may release custody all the same.
+    Redundant,
+    DepletedStorage,
+    NoRoute,
+}
+
+/// Sent by the node a bundle was handed to, accepting or refusing custody of it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustodySignal {
+    pub bundle_id: BundleId,
+    pub node_id: NodeId,
+    pub refusal: Option<CustodyRefusal>,
+}
+
+impl CustodySignal {
+    /// Whether the signalling node holds the bundle, releasing the previous custodian.
+    pub fn releases_custody(&self) -> bool {
+        matches!(self.refusal, None | Some(CustodyRefusal::Redundant))
+    }
+}
+
+impl WireSize for CustodySignal {
+    fn wire_size(&self) -> usize {
+        self.bundle_id.source.wire_size()
+            + self.bundle_id.creation_timestamp.wire_size()
+            + self.bundle_id.fragment_offset.wire_size()
+            + self.node_id.wire_size()
+            + TAG_SIZE
+    }
+}
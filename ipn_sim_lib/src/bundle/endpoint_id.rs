@@ -0,0 +1,84 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ipn_sim::groups::Groups;
+use crate::message_destination::{IsIncluded, MessageDestination};
+use crate::utils::NodeId;
+use crate::wire_size::{WireSize, TAG_SIZE};
+
+/// Names the source, destination or report-to endpoint of a bundle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EndpointId {
+    /// `dtn:none`, the null endpoint.
+    None,
+    /// `ipn:<node>.<service>`
+    Ipn {
+        node_id: NodeId,
+        service_number: u16,
+    },
+}
+
+impl EndpointId {
+    /// The default service endpoint of a node.
+    pub fn ipn(node_id: NodeId) -> Self {
+        EndpointId::Ipn {
+            node_id,
+            service_number: 0,
+        }
+    }
+
+    pub fn node_id(&self) -> Option<NodeId> {
+        match self {
+            EndpointId::None => None,
+            EndpointId::Ipn { node_id, .. } => Some(*node_id),
+        }
+    }
+}
+
+impl fmt::Display for EndpointId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndpointId::None => write!(f, "dtn:none"),
+            EndpointId::Ipn { node_id, service_number } => write!(f, "ipn:{}.{}", node_id, service_number),
+        }
+    }
+}
+
+impl WireSize for EndpointId {
+    fn wire_size(&self) -> usize {
+        TAG_SIZE
+            + match self {
+                EndpointId::None => 0,
+                EndpointId::Ipn { node_id, service_number } => node_id.wire_size() + service_number.wire_size(),
+            }
+    }
+}
+
+impl IsIncluded<&NodeId> for MessageDestination<EndpointId> {
+    fn is_included(&self, other: &NodeId, groups: &Groups) -> bool {
+        match self {
+            MessageDestination::All => true,
+            MessageDestination::Single(endpoint_id) => endpoint_id.node_id() == Some(*other),
+            MessageDestination::Multiple(endpoint_ids) => endpoint_ids
+                .iter()
+                .any(|endpoint_id| endpoint_id.node_id() == Some(*other)),
+            MessageDestination::Multicast(group_id) | MessageDestination::Anycast(group_id) =>
+                groups.is_member(*group_id, *other),
+        }
+    }
+}
+
+impl From<MessageDestination<NodeId>> for MessageDestination<EndpointId> {
+    fn from(destination: MessageDestination<NodeId>) -> Self {
+        match destination {
+            MessageDestination::All => MessageDestination::All,
+            MessageDestination::Single(node_id) => MessageDestination::Single(EndpointId::ipn(node_id)),
+            MessageDestination::Multiple(node_ids) => MessageDestination::Multiple(
+                node_ids.into_iter().map(EndpointId::ipn).collect()
+            ),
+            MessageDestination::Multicast(group_id) => MessageDestination::Multicast(group_id),
+            MessageDestination::Anycast(group_id) => MessageDestination::Anycast(group_id),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+
+use rustc_hash::FxHashMap;
+
+use crate::bundle::block::FragmentOffset;
+use crate::bundle::bundle::{Bundle, BundleId};
+use crate::utils::{Data, TimeMetric};
+
+/// Splits a bundle into fragments carrying at most `max_payload_size` bytes of payload each.
+/// Bundles that fit, or must not be fragmented, are returned whole.
+pub fn fragment(bundle: &Bundle, max_payload_size: usize) -> Vec<Bundle> {
+    if bundle.payload.len() <= max_payload_size || bundle.primary.must_not_fragment || max_payload_size == 0 {
+        return vec![bundle.clone()];
+    }
+
+    let (base_offset, total_length) = bundle.primary.fragment_offset.map_or(
+        (0, bundle.payload.len() as u32),
+        |fragment_offset| (fragment_offset.offset, fragment_offset.total_length),
+    );
+
+    bundle.payload
+        .chunks(max_payload_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut fragment = bundle.clone();
+            fragment.primary.fragment_offset = Some(FragmentOffset {
+                offset: base_offset + (index * max_payload_size) as u32,
+                total_length,
+            });
+            fragment.payload = chunk.into();
+            fragment
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct PartialBundle {
+    first_fragment: Bundle,
+    payloads: BTreeMap<u32, Data>,
+}
+
+/// Collects fragments until the payload of the original bundle is complete.
+#[derive(Clone, Default)]
+pub struct Reassembler {
+    partial_bundles: FxHashMap<BundleId, PartialBundle>,
+}
+
+impl Reassembler {
+    /// Returns the original bundle once the given fragment completes it. Unfragmented bundles are
+    /// returned as is. Fragments extending past the total length, or disagreeing with earlier
+    /// fragments about it, are dropped.
+    pub fn insert(&mut self, bundle: Bundle) -> Option<Bundle> {
+        let fragment_offset = match bundle.primary.fragment_offset {
+            Some(fragment_offset) => fragment_offset,
+            None => return Some(bundle),
+        };
+
+        let fits = (fragment_offset.offset as usize)
+            .checked_add(bundle.payload.len())
+            .is_some_and(|end| end <= fragment_offset.total_length as usize);
+        if !fits {
+            return None;
+        }
+
+        let id = BundleId {
+            fragment_offset: None,
+            ..bundle.id()
+        };
+        let consistent = self.partial_bundles.get(&id).is_none_or(|partial_bundle| {
+            partial_bundle.first_fragment.primary.fragment_offset
+                .is_some_and(|first_offset| first_offset.total_length == fragment_offset.total_length)
+        });
+        if !consistent {
+            return None;
+        }
+
+        let partial_bundle = self.partial_bundles
+            .entry(id)
+            .or_insert_with(|| PartialBundle {
+                first_fragment: bundle.clone(),
+                payloads: BTreeMap::new(),
+            });
+        partial_bundle.payloads.insert(fragment_offset.offset, bundle.payload);
+
+        let mut covered = 0;
+        for (&offset, payload) in &partial_bundle.payloads {
+            if offset > covered {
+                return None;
+            }
+            covered = covered.max(offset + payload.len() as u32);
+        }
+        if covered < fragment_offset.total_length {
+            return None;
+        }
+
+        let partial_bundle = self.partial_bundles.remove(&id).unwrap();
+        let mut payload = vec![0; fragment_offset.total_length as usize];
+        for (offset, fragment_payload) in partial_bundle.payloads {
+            payload[offset as usize..offset as usize + fragment_payload.len()].copy_from_slice(&fragment_payload);
+        }
+
+        let mut bundle = partial_bundle.first_fragment;
+        bundle.primary.fragment_offset = None;
+        bundle.payload = payload.into_boxed_slice();
+        Some(bundle)
+    }
+
+    /// Discards the fragments of bundles that expired before they could be reassembled.
+    pub fn evict_expired(&mut self, time: TimeMetric) {
+        self.partial_bundles.retain(|_, partial_bundle| !partial_bundle.first_fragment.is_expired(time));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bundle::bundle::Bundle;
+    use crate::bundle::fragmentation::{fragment, Reassembler};
+    use crate::message_destination::MessageDestination;
+
+    #[test]
+    fn test_reassembly() {
+        let payload = (0..10).collect::<Vec<u8>>().into_boxed_slice();
+        let bundle = Bundle::new(0, 0, MessageDestination::Single(1), payload.clone(), 0, 100);
+
+        let mut fragments = fragment(&bundle, 4);
+        assert_eq!(fragments.len(), 3);
+        fragments.extend(fragment(&fragments[1], 3));
+        fragments.remove(1);
+
+        let mut reassembler = Reassembler::default();
+        let last_fragment = fragments.remove(0);
+        for fragment in fragments {
+            assert!(reassembler.insert(fragment).is_none());
+        }
+        let reassembled = reassembler.insert(last_fragment).unwrap();
+        assert_eq!(reassembled.payload, payload);
+        assert_eq!(reassembled.primary.fragment_offset, None);
+    }
+
+    #[test]
+    fn test_inconsistent_fragments_dropped() {
+        let payload = (0..10).collect::<Vec<u8>>().into_boxed_slice();
+        let bundle = Bundle::new(0, 0, MessageDestination::Single(1), payload.clone(), 0, 100);
+        let mut fragments = fragment(&bundle, 5);
+
+        let mut reassembler = Reassembler::default();
+        assert!(reassembler.insert(fragments[0].clone()).is_none());
+
+        let mut overlong = fragments[1].clone();
+        overlong.payload = (0..8).collect::<Vec<u8>>().into_boxed_slice();
+        assert!(reassembler.insert(overlong).is_none());
+
+        let mut other_total = fragments[1].clone();
+        other_total.primary.fragment_offset.as_mut().unwrap().total_length = 8;
+        other_total.payload = (5..8).collect::<Vec<u8>>().into_boxed_slice();
+        assert!(reassembler.insert(other_total).is_none());
+
+        assert_eq!(reassembler.insert(fragments.remove(1)).unwrap().payload, payload);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod endpoint_id;
+pub mod block;
+pub mod bundle;
+pub mod fragmentation;
+pub mod status_report;
+pub mod custody;
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::binary_serde::BinarySerde;
+use crate::bundle::bundle::{Bundle, BundleId};
+use crate::message_destination::MessageDestination;
+use crate::utils::{NodeId, TimeMetric};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeletionReason {
+    LifetimeExpired,
+    HopLimitExceeded,
+    DepletedStorage,
+    NoRoute,
+    TransmissionCancelled,
+}
+
+/// A step in a bundle's life at a node. Each maps onto a
+/// [`RouterEventType`](crate::events::router_event::RouterEventType) when reported through
+/// [`RouterLink::report_bundle_status`](crate::router_link::RouterLink::report_bundle_status).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BundleStatus {
+    Received {
+        from_id: NodeId,
+    },
+    Forwarded {
+        to_id: NodeId,
+    },
+    Delivered,
+    Deleted(DeletionReason),
+}
+
+/// Administrative record informing a bundle's report-to endpoint of its status at a node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatusReport {
+    pub bundle_id: BundleId,
+    pub node_id: NodeId,
+    pub status: BundleStatus,
+    pub time: TimeMetric,
+}
+
+impl StatusReport {
+    /// Wraps the report in an administrative bundle sent from `node_id` to `bundle`'s report-to
+    /// endpoint, if it requested status reports and has one.
+    pub fn into_bundle(self, bundle: &Bundle, sequence_number: u16, lifetime: TimeMetric) -> Option<Bundle> {
+        if !bundle.primary.status_reports_requested {
+            return None;
+        }
+        let report_to_id = bundle.primary.report_to.node_id()?;
+        let mut report_bundle = Bundle::new(
+            self.node_id,
+            sequence_number,
+            MessageDestination::Single(report_to_id),
+            self.as_data(),
+            self.time,
+            lifetime,
+        );
+        report_bundle.primary.is_admin_record = true;
+        Some(report_bundle)
+    }
+}
+
+impl BinarySerde for StatusReport {}
@@ -0,0 +1,28 @@
+use crate::event::Event;
+use crate::ipn_sim::ipn_sim::IpnSim;
+use crate::node::node::Node;
+use crate::utils::{GroupId, Shared};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MembershipChange {
+    Join,
+    Leave,
+}
+
+/// Changes the membership of a group, e.g. a ground station joining once it is commissioned.
+#[derive(Clone)]
+pub struct GroupMembershipEvent {
+    pub node: Shared<Node>,
+    pub group_id: GroupId,
+    pub change: MembershipChange,
+}
+
+impl Event for GroupMembershipEvent {
+    fn handle(self: Box<Self>, sim: &mut IpnSim) {
+        let node_id = self.node.borrow().id;
+        match self.change {
+            MembershipChange::Join => sim.groups.join(self.group_id, node_id),
+            MembershipChange::Leave => sim.groups.leave(self.group_id, node_id),
+        }
+    }
+}
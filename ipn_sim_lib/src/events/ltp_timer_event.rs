@@ -0,0 +1,42 @@
+use std::rc::Rc;
+
+use crate::event::Event;
+use crate::ipn_sim::ipn_sim::IpnSim;
+use crate::ltp::engine::round_trip_time;
+use crate::node::node::Node;
+use crate::utils::Shared;
+
+/// Fires when an LTP report is overdue for the given checkpoint of an export session.
+#[derive(Clone)]
+pub struct LtpTimerEvent {
+    pub node: Shared<Node>,
+    pub session_number: u32,
+    pub checkpoint_serial: u32,
+}
+
+impl LtpTimerEvent {
+    pub fn new(node: &Shared<Node>, session_number: u32, checkpoint_serial: u32) -> Self {
+        Self {
+            node: Rc::clone(node),
+            session_number,
+            checkpoint_serial,
+        }
+    }
+}
+
+impl Event for LtpTimerEvent {
+    fn handle(self: Box<Self>, sim: &mut IpnSim) {
+        let mut node_ref = self.node.borrow_mut();
+        let position = node_ref.position;
+        let round_trip_time = |destination_id| round_trip_time(position, sim.get_node(destination_id).borrow().position);
+        let actions = node_ref.ltp_engine
+            .as_mut()
+            .unwrap()
+            .on_timer(self.session_number, self.checkpoint_serial, round_trip_time);
+        actions.dispatch(&mut node_ref, &self.node, sim);
+    }
+
+    fn is_internal() -> bool where Self: Sized {
+        true
+    }
+}
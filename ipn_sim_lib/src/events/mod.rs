@@ -1,5 +1,7 @@
 pub mod awake_router_event;
 pub mod create_message_event;
+pub mod group_membership_event;
+pub mod ltp_timer_event;
 pub mod receive_data_event;
 pub mod router_event;
 pub mod transmit_end_event;
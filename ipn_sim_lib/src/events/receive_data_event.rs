@@ -5,24 +5,51 @@ use crate::event::Event;
 use crate::ipn_sim::ipn_sim::IpnSim;
 use crate::node::node::Node;
 use crate::router_link::RouterLink;
-use crate::utils::Data;
+use crate::ltp::segment::Segment;
+use crate::packet::PacketData;
 use crate::utils::Shared;
 
 
 #[derive(Clone)]
 pub struct ReceiveDataEvent {
     pub node: Shared<Node>,
-    pub data: Data,
+    pub packet: PacketData,
 }
 
 impl Event for ReceiveDataEvent {
     fn handle(self: Box<Self>, sim: &mut IpnSim) {
         let mut node_ref = self.node.borrow_mut();
+
+        // Segments are only meaningful to an LTP engine; nodes without one ignore them.
+        let packets = if !Segment::is_segment(&self.packet) {
+            vec![self.packet]
+        } else if node_ref.ltp_engine.is_none() {
+            vec![]
+        } else {
+            match self.packet.decode::<Segment>() {
+                Ok(segment) => {
+                    let node_id = node_ref.id;
+                    let actions = node_ref.ltp_engine
+                        .as_mut()
+                        .unwrap()
+                        .on_segment(node_id, segment, sim.time);
+                    actions.dispatch(&mut node_ref, &self.node, sim)
+                }
+                Err(error) => {
+                    RouterLink::new(&mut node_ref, &self.node, sim)
+                        .log(format!("Dropped undecodable LTP segment: {}", error));
+                    vec![]
+                }
+            }
+        };
+
         let mut router = node_ref.router.take().unwrap();
-        router.on_data_received(
-            &mut RouterLink::new(&mut node_ref, &self.node, sim),
-            self.data,
-        );
+        for packet in packets {
+            router.on_packet_data_received(
+                &mut RouterLink::new(&mut node_ref, &self.node, sim),
+                packet,
+            );
+        }
         node_ref.router = Some(router);
     }
 
@@ -38,6 +38,11 @@ pub enum RouterEventType {
         id: MessageId,
         source_node: Shared<Node>,
     },
+    /// A held message was not handed to a neighbour whose summary vector claimed it.
+    MessageSuppressed {
+        id: MessageId,
+        neighbour_node: Shared<Node>,
+    },
 }
 
 impl RouterEvent {
@@ -2,8 +2,10 @@ use crate::event::Event;
 use crate::events::receive_data_event::ReceiveDataEvent;
 use crate::ipn_sim::ipn_sim::IpnSim;
 use crate::node::node::Node;
-use crate::utils::{Data, TimeMetric, C};
+use crate::packet::PacketData;
+use crate::utils::{TimeMetric, C};
 use cgmath::MetricSpace;
+use rand::random;
 use std::cell::RefCell;
 use std::rc::Rc;
 use crate::utils::Shared;
@@ -12,7 +14,7 @@ use crate::utils::Shared;
 #[derive(Clone)]
 pub struct TransmitEndEvent {
     pub node: Shared<Node>,
-    pub data: Data,
+    pub packet: PacketData,
 }
 
 impl Event for TransmitEndEvent {
@@ -25,14 +27,16 @@ impl Event for TransmitEndEvent {
                 continue;
             }
             let can_transceive = sim.nodes_can_transceive(&*node_ref, &*receiving_node_ref);
-            if can_transceive {
+            let lost = node_ref.transceiver.loss_probability > 0.
+                && random::<f64>() < node_ref.transceiver.loss_probability;
+            if can_transceive && !lost {
                 let flight_time =
                     (node_ref.position.distance(receiving_node_ref.position) / C) as TimeMetric;
                 sim.add_event(
                     sim.time + flight_time,
                     ReceiveDataEvent {
                         node: Rc::clone(receiving_node),
-                        data: self.data.clone(),
+                        packet: self.packet.clone(),
                     },
                 )
             }
@@ -2,6 +2,7 @@ use crate::event::Event;
 use crate::events::transmit_end_event::TransmitEndEvent;
 use crate::ipn_sim::ipn_sim::IpnSim;
 use crate::node::node::Node;
+use crate::packet::PacketData;
 use std::cell::RefCell;
 use std::rc::Rc;
 use crate::utils::Shared;
@@ -12,15 +13,28 @@ pub struct TransmitStartEvent {
     pub node: Shared<Node>,
 }
 
+impl TransmitStartEvent {
+    /// Queues a packet on the node's transceiver, to be transmitted once it is free.
+    pub fn schedule(node: &mut Node, node_rc: &Shared<Node>, sim: &mut IpnSim, packet: PacketData) {
+        let transmit_start = node.transceiver.add_to_buffer(packet, sim.time);
+        sim.add_event(
+            transmit_start,
+            TransmitStartEvent {
+                node: Rc::clone(node_rc),
+            },
+        );
+    }
+}
+
 impl Event for TransmitStartEvent {
     fn handle(self: Box<Self>, sim: &mut IpnSim) {
         let mut node_ref = self.node.borrow_mut();
-        let data = node_ref.transceiver.pop_head_data();
+        let packet = node_ref.transceiver.pop_head_packet();
         sim.add_event(
-            sim.time + node_ref.transceiver.get_transmit_time(&data),
+            sim.time + node_ref.transceiver.get_transmit_time(&packet),
             TransmitEndEvent {
                 node: Rc::clone(&self.node),
-                data,
+                packet,
             },
         )
     }
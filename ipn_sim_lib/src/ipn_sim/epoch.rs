@@ -0,0 +1,50 @@
+use crate::utils::{SpaceMetric, TimeMetric};
+
+const NANOSECONDS_PER_DAY: SpaceMetric = 1_000_000_000. * 86_400.;
+
+/// A UTC instant as a Julian date, used to map sim time onto calendar time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Epoch {
+    pub julian_date: SpaceMetric,
+}
+
+impl Epoch {
+    /// 2000-01-01 12:00 UTC.
+    pub const J2000: Epoch = Epoch { julian_date: 2_451_545. };
+
+    pub fn from_utc(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: SpaceMetric) -> Self {
+        let (year, month) = (year as SpaceMetric, month as SpaceMetric);
+        Self {
+            julian_date: 367. * year
+                - (7. * (year + ((month + 9.) / 12.).floor()) * 0.25).floor()
+                + (275. * month / 9.).floor()
+                + day as SpaceMetric
+                + 1_721_013.5
+                + ((second / 60. + minute as SpaceMetric) / 60. + hour as SpaceMetric) / 24.,
+        }
+    }
+
+    /// `day_of_year` counts from 1 at midnight on the 1st of January, as in two line element sets.
+    pub fn from_day_of_year(year: i32, day_of_year: SpaceMetric) -> Self {
+        Self {
+            julian_date: Self::from_utc(year, 1, 0, 0, 0, 0.).julian_date + day_of_year,
+        }
+    }
+
+    /// The instant `time` into a simulation starting at this epoch.
+    pub fn at_sim_time(&self, time: TimeMetric) -> Self {
+        Self {
+            julian_date: self.julian_date + time as SpaceMetric / NANOSECONDS_PER_DAY,
+        }
+    }
+
+    pub fn minutes_since(&self, other: &Epoch) -> SpaceMetric {
+        (self.julian_date - other.julian_date) * 1440.
+    }
+}
+
+impl Default for Epoch {
+    fn default() -> Self {
+        Self::J2000
+    }
+}
@@ -0,0 +1,96 @@
+use rustc_hash::FxHashSet;
+
+use crate::utils::{GroupId, NodeId};
+
+#[derive(Clone, Default)]
+pub struct Group {
+    pub name: String,
+    pub members: FxHashSet<NodeId>,
+}
+
+/// Named groups of nodes addressed by multicast and anycast destinations. Group ids are assigned
+/// in the order groups are added.
+#[derive(Clone, Default)]
+pub struct Groups {
+    groups: Vec<Group>,
+}
+
+impl Groups {
+    pub fn add(&mut self, name: impl Into<String>, members: impl IntoIterator<Item = NodeId>) -> GroupId {
+        self.groups.push(Group {
+            name: name.into(),
+            members: members.into_iter().collect(),
+        });
+        (self.groups.len() - 1) as GroupId
+    }
+
+    pub fn get(&self, group_id: GroupId) -> Option<&Group> {
+        self.groups.get(group_id as usize)
+    }
+
+    pub fn id_of(&self, name: &str) -> Option<GroupId> {
+        self.groups
+            .iter()
+            .position(|group| group.name == name)
+            .map(|index| index as GroupId)
+    }
+
+    /// Panics if the group was never added.
+    pub fn join(&mut self, group_id: GroupId, node_id: NodeId) {
+        self.get_mut(group_id).members.insert(node_id);
+    }
+
+    /// Panics if the group was never added.
+    pub fn leave(&mut self, group_id: GroupId, node_id: NodeId) {
+        self.get_mut(group_id).members.remove(&node_id);
+    }
+
+    pub fn is_member(&self, group_id: GroupId, node_id: NodeId) -> bool {
+        self.get(group_id).is_some_and(|group| group.members.contains(&node_id))
+    }
+
+    fn get_mut(&mut self, group_id: GroupId) -> &mut Group {
+        let group_count = self.groups.len();
+        self.groups
+            .get_mut(group_id as usize)
+            .unwrap_or_else(|| panic!("no group {} among the {} groups added", group_id, group_count))
+    }
+
+    /// Current members, in ascending order of node id.
+    pub fn members(&self, group_id: GroupId) -> Vec<NodeId> {
+        let mut members = self.get(group_id)
+            .map_or(vec![], |group| group.members.iter().copied().collect());
+        members.sort_unstable();
+        members
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ipn_sim::groups::Groups;
+    use crate::message_destination::{IsIncluded, MessageDestination};
+    use crate::utils::NodeId;
+
+    #[test]
+    fn test_group_destinations() {
+        let mut groups = Groups::default();
+        let ground_stations = groups.add("ground stations", vec![3, 1]);
+        assert_eq!(groups.id_of("ground stations"), Some(ground_stations));
+
+        let destination = MessageDestination::<NodeId>::Anycast(ground_stations);
+        assert!(destination.is_included(&1, &groups));
+        assert!(!destination.is_included(&2, &groups));
+
+        groups.join(ground_stations, 2);
+        groups.leave(ground_stations, 3);
+        assert_eq!(MessageDestination::<NodeId>::Multicast(ground_stations).node_ids(&groups), Some(vec![1, 2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "no group 1")]
+    fn test_join_unknown_group() {
+        let mut groups = Groups::default();
+        groups.add("ground stations", vec![]);
+        groups.join(1, 0);
+    }
+}
@@ -1,20 +1,23 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use cgmath::{InnerSpace, MetricSpace};
-
 use crate::body::Body;
 use crate::event::Event;
+use crate::ipn_sim::epoch::Epoch;
+use crate::ipn_sim::groups::Groups;
 use crate::ipn_sim::tick_result::TickResult;
+use crate::movements::surface_movement::SurfaceMovement;
 use crate::node::node::Node;
+use crate::occluder::Occluder;
 use crate::report::Report;
 use crate::router::Router;
 use crate::router_link::RouterLink;
 use crate::schedule::schedule::Schedule;
 use crate::utils;
-use crate::utils::{Data, NodeId, TimeMetric};
+use crate::utils::{MessageId, NodeId, TimeMetric};
+use crate::oracle::oracle::ContactPlan;
+use rustc_hash::FxHashMap;
 use bit_vec::BitVec;
-use collision::{Continuous, Ray, Sphere};
 use std::mem::forget;
 use crate::utils::Shared;
 
@@ -26,6 +29,11 @@ pub struct IpnSim {
     pub reports: Option<Vec<Shared<dyn Report>>>,
     pub nodes: Option<Vec<Shared<Node>>>,
     pub bodies: Vec<Shared<Body>>,
+    pub delivered_messages: FxHashMap<MessageId, Vec<NodeId>>,
+    pub contact_plan: Option<ContactPlan>,
+    pub groups: Groups,
+    pub epoch: Epoch,
+    pub occluders: Vec<Box<dyn Occluder>>,
 }
 
 impl IpnSim {
@@ -118,6 +126,11 @@ impl IpnSim {
     }
 
     pub fn nodes_can_transceive(&self, transmitting_node: &Node, receiving_node: &Node) -> bool {
+        self.nodes_can_transceive_holding(transmitting_node, receiving_node, &[])
+    }
+
+    /// Like `nodes_can_transceive`, for callers that have other nodes borrowed mutably.
+    pub fn nodes_can_transceive_holding(&self, transmitting_node: &Node, receiving_node: &Node, held_nodes: &[&Node]) -> bool {
         transmitting_node
             .transceiver
             .guard
@@ -126,30 +139,22 @@ impl IpnSim {
                 .transceiver
                 .guard
                 .can_transceive(receiving_node, transmitting_node)
-            && self.nodes_are_covisible(transmitting_node, receiving_node)
+            && self.nodes_are_covisible(transmitting_node, receiving_node, held_nodes)
     }
 
-    pub fn nodes_are_covisible(&self, transmitting_node: &Node, receiving_node: &Node) -> bool {
+    pub fn nodes_are_covisible(&self, transmitting_node: &Node, receiving_node: &Node, held_nodes: &[&Node]) -> bool {
         let source_position = transmitting_node.position;
         let destination_position = receiving_node.position;
-        let ray = Ray::new(
-            source_position,
-            (destination_position - source_position).normalize(),
-        );
-        for body in &self.bodies {
-            let body = body.borrow();
-            let collider = Sphere {
-                center: body.position,
-                radius: body.radius,
-            };
-            if let Some(intersection) = collider.intersection(&ray) {
-                if source_position.distance2(intersection)
-                    < source_position.distance2(destination_position)
-                {
-                    return false;
-                }
-            }
-        }
-        true
+        !self.bodies
+            .iter()
+            .any(|body| body.borrow().occludes_between(
+                source_position,
+                SurfaceMovement::is_on(transmitting_node, body),
+                destination_position,
+                SurfaceMovement::is_on(receiving_node, body),
+            ))
+            && !self.occluders
+                .iter()
+                .any(|occluder| occluder.occludes(transmitting_node, receiving_node, held_nodes))
     }
 }
\ No newline at end of file
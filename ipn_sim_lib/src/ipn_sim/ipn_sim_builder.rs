@@ -1,19 +1,27 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::body::Body;
+use crate::body::{Body, BodyRotation};
 use crate::event::Event;
+use crate::ipn_sim::epoch::Epoch;
+use crate::ipn_sim::groups::Groups;
 use crate::ipn_sim::ipn_sim::IpnSim;
 use crate::node::message_buffer::MessageBuffer;
 use crate::movement::Movement;
+use crate::movements::n_body::NBodyPropagator;
+use crate::movements::static_movement::StaticMovement;
 use crate::node::node::Node;
+use crate::occluder::Occluder;
 use crate::report::Report;
 use crate::router::Router;
 use crate::schedule::schedule::Schedule;
+use crate::solar_system;
 use crate::transceiver::transceiver::Transceiver;
-use crate::utils::{NodeId, shared, SpaceMetric, TimeMetric};
+use crate::utils::{GroupId, NodeId, shared, SpaceMetric, TimeMetric};
 use crate::utils::Shared;
 use crate::node::node_builder::NodeBuilder;
+use rustc_hash::FxHashMap;
+use cgmath::{EuclideanSpace, Point3};
 
 pub struct IpnSimBuilder {
     sim_length: TimeMetric,
@@ -21,6 +29,10 @@ pub struct IpnSimBuilder {
     schedule: Schedule<TimeMetric, Box<dyn Event>>,
     nodes: Vec<Shared<Node>>,
     bodies: Vec<Shared<Body>>,
+    groups: Groups,
+    epoch: Epoch,
+    n_body_step: Option<TimeMetric>,
+    occluders: Vec<Box<dyn Occluder>>,
 }
 
 impl IpnSimBuilder {
@@ -31,6 +43,10 @@ impl IpnSimBuilder {
             schedule: Schedule::new(),
             nodes: vec![],
             bodies: vec![],
+            groups: Groups::default(),
+            epoch: Epoch::default(),
+            n_body_step: None,
+            occluders: vec![],
         }
     }
 
@@ -63,11 +79,22 @@ impl IpnSimBuilder {
     }
 
     pub fn add_body(
+        self,
+        name: impl Into<String>,
+        mass: SpaceMetric,
+        movement: impl Movement + 'static,
+        radius: SpaceMetric,
+    ) -> Self {
+        self.add_rotating_body(name, mass, movement, radius, BodyRotation::default())
+    }
+
+    pub fn add_rotating_body(
         mut self,
         name: impl Into<String>,
         mass: SpaceMetric,
         movement: impl Movement + 'static,
         radius: SpaceMetric,
+        rotation: BodyRotation,
     ) -> Self {
         self.bodies.push(shared(Body {
             name: name.into(),
@@ -75,10 +102,101 @@ impl IpnSimBuilder {
             position: movement.get_position_at(0),
             movement: Box::new(movement),
             radius,
+            rotation,
+            flattening: 0.,
+            grazing_margin: 0.,
         }));
         self
     }
 
+    /// Makes a body an oblate spheroid, its radius being the equatorial radius.
+    pub fn oblate_body(self, name: &str, flattening: SpaceMetric) -> Self {
+        self.get_body_by_name(name).borrow_mut().flattening = flattening;
+        self
+    }
+
+    /// Rejects links passing less than `grazing_margin` above the body's surface.
+    pub fn body_grazing_margin(self, name: &str, grazing_margin: SpaceMetric) -> Self {
+        self.get_body_by_name(name).borrow_mut().grazing_margin = grazing_margin;
+        self
+    }
+
+    /// Adds an occluder that links are tested against besides the bodies.
+    pub fn add_occluder(mut self, occluder: impl Occluder + 'static) -> Self {
+        self.occluders.push(Box::new(occluder));
+        self
+    }
+
+    /// Adds bodies from the solar system catalogue, in catalogue order, the giant planets, Earth and
+    /// Mars being oblate. A body orbits its parent if the parent is also added, and the one body
+    /// without its parent is fixed at the origin.
+    pub fn add_catalogue_bodies(mut self, names: &[&str]) -> Self {
+        for name in names {
+            assert!(
+                solar_system::CATALOGUE.iter().any(|body| body.name == *name),
+                "{} is not in the solar system catalogue",
+                name,
+            );
+        }
+        let mut root = None;
+        for catalogue_body in solar_system::CATALOGUE.iter().filter(|body| names.contains(&body.name)) {
+            let parent = catalogue_body.parent
+                .filter(|parent| names.contains(parent))
+                .map(|parent| self.get_body_by_name(parent));
+            let rotation = catalogue_body.rotation_at(self.epoch);
+            self = match parent {
+                Some(parent) => {
+                    let movement = catalogue_body.movement_around(&parent, self.epoch);
+                    self.add_rotating_body(catalogue_body.name, catalogue_body.mass, movement, catalogue_body.radius, rotation)
+                }
+                None => {
+                    if let Some(root) = root {
+                        panic!("{} and {} both need their parent to be added", root, catalogue_body.name);
+                    }
+                    root = Some(catalogue_body.name);
+                    self.add_rotating_body(
+                        catalogue_body.name,
+                        catalogue_body.mass,
+                        StaticMovement::new(Point3::origin()),
+                        catalogue_body.radius,
+                        rotation,
+                    )
+                }
+            };
+            self = self.oblate_body(catalogue_body.name, catalogue_body.flattening);
+        }
+        assert_eq!(root.is_some(), !names.is_empty(), "unknown catalogue body in {:?}", names);
+        self
+    }
+
+    /// Adds a named group of nodes. Groups are given ids in the order they are added.
+    pub fn add_group(mut self, name: impl Into<String>, member_ids: impl IntoIterator<Item = NodeId>) -> Self {
+        self.groups.add(name, member_ids);
+        self
+    }
+
+    pub fn get_group_id(&self, name: &str) -> GroupId {
+        self.groups.id_of(name).unwrap()
+    }
+
+    /// The calendar instant at sim time zero.
+    pub fn epoch(mut self, epoch: Epoch) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    pub fn get_epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    /// Propagates the bodies and free flying nodes under gravity with a fixed `step` when the
+    /// simulation is built. See [`NBodyPropagator`].
+    pub fn n_body(mut self, step: TimeMetric) -> Self {
+        assert!(step > 0, "n-body step must be positive");
+        self.n_body_step = Some(step);
+        self
+    }
+
     pub fn get_node(&self, node_index: usize) -> Shared<Node> {
         Rc::clone(&self.nodes[node_index])
     }
@@ -87,7 +205,26 @@ impl IpnSimBuilder {
         Rc::clone(&self.bodies[body_index])
     }
 
+    /// Panics if no body of that name has been added.
+    pub fn get_body_by_name(&self, name: &str) -> Shared<Body> {
+        let body = self.bodies
+            .iter()
+            .find(|body| body.borrow().name == name)
+            .unwrap_or_else(|| panic!("no body named {} has been added", name));
+        Rc::clone(body)
+    }
+
     pub fn build(self) -> IpnSim {
+        if let Some(step) = self.n_body_step {
+            NBodyPropagator::new(step).propagate(&self.bodies, &self.nodes, self.sim_length);
+            for body in &self.bodies {
+                body.borrow_mut().set_position(0);
+            }
+            for node in &self.nodes {
+                node.borrow_mut().set_position(0);
+            }
+        }
+
         IpnSim {
             time: 0,
             length: self.sim_length,
@@ -99,6 +236,11 @@ impl IpnSimBuilder {
             },
             nodes: Some(self.nodes),
             bodies: self.bodies,
+            delivered_messages: FxHashMap::default(),
+            contact_plan: None,
+            groups: self.groups,
+            epoch: self.epoch,
+            occluders: self.occluders,
         }
     }
 }
@@ -1,3 +1,5 @@
+pub mod epoch;
+pub mod groups;
 pub mod ipn_sim;
 pub mod ipn_sim_builder;
 pub mod tick_result;
@@ -7,17 +7,25 @@ pub use downcast_rs;
 
 pub mod binary_serde;
 pub mod body;
+pub mod bundle;
 pub mod event;
 pub mod events;
 pub mod ipn_sim;
+pub mod ltp;
 pub mod movement;
 pub mod movements;
 pub mod node;
+pub mod occluder;
+pub mod occluders;
+pub mod oracle;
+pub mod packet;
 pub mod report;
 pub mod router;
 pub mod router_link;
 pub mod routers;
 pub mod schedule;
+pub mod solar_system;
 pub mod transceiver;
 pub mod utils;
 pub mod message_destination;
+pub mod wire_size;
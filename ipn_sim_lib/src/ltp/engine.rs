@@ -0,0 +1,380 @@
+use cgmath::{MetricSpace, Point3};
+use rustc_hash::FxHashMap;
+
+use crate::events::ltp_timer_event::LtpTimerEvent;
+use crate::events::transmit_start_event::TransmitStartEvent;
+
+use crate::ipn_sim::ipn_sim::IpnSim;
+use crate::ltp::export_session::ExportSession;
+use crate::ltp::import_session::ImportSession;
+use crate::ltp::segment::{Range, Segment};
+use crate::node::node::Node;
+use crate::packet::PacketData;
+use crate::utils::{NodeId, Shared, SpaceMetric, TimeMetric, C};
+
+/// Which part of a unicast block is transmitted reliably. Broadcast blocks are always green.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedPart {
+    All,
+    /// The packet header is red, the payload green.
+    Header,
+    None,
+}
+
+#[derive(Clone)]
+pub struct LtpConfig {
+    pub red_part: RedPart,
+    /// Added to the round trip light time when waiting for a report.
+    pub report_timeout_margin: TimeMetric,
+    /// Checkpoint retransmissions before a session is abandoned.
+    pub max_retransmissions: u8,
+    /// Incomplete import sessions idle for this long are discarded.
+    pub session_timeout: TimeMetric,
+}
+
+impl Default for LtpConfig {
+    fn default() -> Self {
+        Self {
+            red_part: RedPart::All,
+            report_timeout_margin: 1_000_000_000,
+            max_retransmissions: 5,
+            session_timeout: 1_000_000_000 * 60 * 60,
+        }
+    }
+}
+
+/// What the engine asks of the node after handling a block, segment or timer.
+#[derive(Default)]
+pub struct LtpActions {
+    pub segments: Vec<Segment>,
+    /// Report timers as the session, checkpoint serial and how long after the queued segments
+    /// have been transmitted they expire.
+    pub timers: Vec<(u32, u32, TimeMetric)>,
+    /// Blocks received in full, to be handed to the router.
+    pub blocks: Vec<PacketData>,
+}
+
+impl LtpActions {
+    /// Queues the segments on the node's transceiver and schedules the timers, returning the
+    /// received blocks.
+    pub fn dispatch(self, node: &mut Node, node_rc: &Shared<Node>, sim: &mut IpnSim) -> Vec<PacketData> {
+        for segment in self.segments {
+            TransmitStartEvent::schedule(node, node_rc, sim, PacketData::from_packet(segment));
+        }
+        for (session_number, checkpoint_serial, timeout) in self.timers {
+            sim.add_event(
+                node.transceiver.busy_until.max(sim.time) + timeout,
+                LtpTimerEvent::new(node_rc, session_number, checkpoint_serial),
+            );
+        }
+        self.blocks
+    }
+}
+
+/// A Licklider Transmission Protocol style convergence layer. Blocks are segmented, the red part
+/// of unicast blocks is checkpointed and retransmitted until reported received, and green parts
+/// are sent once.
+pub struct LtpEngine {
+    pub config: LtpConfig,
+    next_session_number: u32,
+    export_sessions: FxHashMap<u32, ExportSession>,
+    import_sessions: FxHashMap<(NodeId, u32), ImportSession>,
+    /// When each session received in full completed, kept for the session timeout so that late
+    /// checkpoints are still answered.
+    completed_sessions: FxHashMap<(NodeId, u32), TimeMetric>,
+}
+
+impl LtpEngine {
+    pub fn new(config: LtpConfig) -> Self {
+        Self {
+            config,
+            next_session_number: 0,
+            export_sessions: FxHashMap::default(),
+            import_sessions: FxHashMap::default(),
+            completed_sessions: FxHashMap::default(),
+        }
+    }
+
+    /// Segments a block sent by `node_id`, to `destination_id` or broadcast, into segments
+    /// carrying at most `max_segment_size` block bytes.
+    pub fn send_block(
+        &mut self,
+        node_id: NodeId,
+        destination_id: Option<NodeId>,
+        packet: PacketData,
+        max_segment_size: usize,
+        round_trip_time: TimeMetric,
+    ) -> LtpActions {
+        let session_number = self.next_session_number;
+        self.next_session_number += 1;
+
+        let block_length = packet.size.total();
+        let red_length = match (destination_id, self.config.red_part) {
+            (None, _) | (_, RedPart::None) => 0,
+            (_, RedPart::All) => block_length,
+            (_, RedPart::Header) => packet.size.header,
+        };
+
+        let mut ranges = split(0, red_length, max_segment_size);
+        ranges.extend(split(red_length, block_length, max_segment_size));
+        if ranges.is_empty() {
+            ranges.push(Range { offset: 0, length: 0 });
+        }
+
+        let data = &packet.data;
+        let data_offset = |offset: usize| offset * data.len() / block_length.max(1);
+        let last_red_index = ranges.iter().rposition(|range| range.offset < red_length);
+
+        let segments = ranges
+            .iter()
+            .enumerate()
+            .map(|(index, range)| Segment::Data {
+                source_id: node_id,
+                destination_id,
+                session_number,
+                range: *range,
+                block_length,
+                red_length,
+                checkpoint_serial: (Some(index) == last_red_index).then_some(0),
+                data: if block_length == 0 {
+                    data.clone()
+                } else {
+                    data[data_offset(range.offset)..data_offset(range.end())].into()
+                },
+                block_type: packet.packet_type,
+                block_size: packet.size,
+            })
+            .collect::<Vec<_>>();
+
+        let mut actions = LtpActions::default();
+        if red_length > 0 {
+            self.export_sessions.insert(session_number, ExportSession {
+                segments: segments.clone(),
+                red_length,
+                destination_id: destination_id.unwrap(),
+                round_trip_time,
+                checkpoint_serial: 0,
+                retransmissions: 0,
+            });
+            actions.timers.push((session_number, 0, round_trip_time + self.config.report_timeout_margin));
+        }
+        actions.segments = segments;
+        actions
+    }
+
+    pub fn on_segment(&mut self, node_id: NodeId, segment: Segment, time: TimeMetric) -> LtpActions {
+        let mut actions = LtpActions::default();
+        match segment {
+            Segment::Data {
+                source_id,
+                destination_id,
+                session_number,
+                range,
+                block_length,
+                red_length,
+                checkpoint_serial,
+                data,
+                block_type,
+                block_size,
+            } => {
+                if destination_id.is_some_and(|destination_id| destination_id != node_id) {
+                    return actions;
+                }
+                let key = (source_id, session_number);
+
+                let session_timeout = self.config.session_timeout;
+                self.import_sessions.retain(|_, import_session| import_session.last_activity + session_timeout > time);
+                self.completed_sessions.retain(|_, &mut completion_time| completion_time + session_timeout > time);
+
+                if self.completed_sessions.contains_key(&key) {
+                    if let Some(checkpoint_serial) = checkpoint_serial {
+                        actions.segments.push(Segment::Report {
+                            source_id: node_id,
+                            destination_id: source_id,
+                            session_number,
+                            checkpoint_serial,
+                            claims: vec![Range { offset: 0, length: red_length }],
+                        });
+                    }
+                    return actions;
+                }
+
+                let import_session = self.import_sessions
+                    .entry(key)
+                    .or_insert_with(|| ImportSession::new(block_length, red_length, block_type, block_size, time));
+                import_session.insert(range, data, time);
+
+                if let Some(checkpoint_serial) = checkpoint_serial {
+                    actions.segments.push(Segment::Report {
+                        source_id: node_id,
+                        destination_id: source_id,
+                        session_number,
+                        checkpoint_serial,
+                        claims: import_session.red_claims(),
+                    });
+                }
+
+                if import_session.is_complete() {
+                    let import_session = self.import_sessions.remove(&key).unwrap();
+                    self.completed_sessions.insert(key, time);
+                    actions.blocks.push(import_session.into_block());
+                }
+            }
+            Segment::Report { destination_id, session_number, claims, .. } => {
+                if destination_id != node_id {
+                    return actions;
+                }
+                let report_timeout_margin = self.config.report_timeout_margin;
+                if let Some(export_session) = self.export_sessions.get_mut(&session_number) {
+                    let unclaimed = export_session.unclaimed_red_segments(&claims);
+                    if unclaimed.is_empty() {
+                        self.export_sessions.remove(&session_number);
+                    } else {
+                        actions.timers.push((
+                            session_number,
+                            export_session.checkpoint_serial,
+                            export_session.round_trip_time + report_timeout_margin,
+                        ));
+                        actions.segments = unclaimed;
+                    }
+                }
+            }
+        }
+        actions
+    }
+
+    /// Resends the checkpoint of a session whose report is overdue, abandoning the session once
+    /// retransmissions are exhausted. The new report timer allows for the current round trip time
+    /// to the session's destination, as given by `round_trip_time`, since the nodes may have
+    /// moved since the block was sent.
+    pub fn on_timer(
+        &mut self,
+        session_number: u32,
+        checkpoint_serial: u32,
+        round_trip_time: impl Fn(NodeId) -> TimeMetric,
+    ) -> LtpActions {
+        let mut actions = LtpActions::default();
+        let max_retransmissions = self.config.max_retransmissions;
+        let report_timeout_margin = self.config.report_timeout_margin;
+
+        if let Some(export_session) = self.export_sessions.get_mut(&session_number) {
+            if export_session.checkpoint_serial != checkpoint_serial {
+                return actions;
+            }
+            if export_session.retransmissions >= max_retransmissions {
+                self.export_sessions.remove(&session_number);
+                return actions;
+            }
+            export_session.retransmissions += 1;
+            export_session.round_trip_time = round_trip_time(export_session.destination_id);
+            actions.segments.push(export_session.checkpoint());
+            actions.timers.push((
+                session_number,
+                export_session.checkpoint_serial,
+                export_session.round_trip_time + report_timeout_margin,
+            ));
+        }
+        actions
+    }
+
+    pub fn export_session_count(&self) -> usize {
+        self.export_sessions.len()
+    }
+}
+
+/// Round trip light time between two positions.
+pub fn round_trip_time(position: Point3<SpaceMetric>, other_position: Point3<SpaceMetric>) -> TimeMetric {
+    2 * (position.distance(other_position) / C) as TimeMetric
+}
+
+/// Splits `[start, end)` into ranges of at most `max_length`.
+fn split(start: usize, end: usize, max_length: usize) -> Vec<Range> {
+    (start..end)
+        .step_by(max_length.max(1))
+        .map(|offset| Range {
+            offset,
+            length: max_length.max(1).min(end - offset),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ltp::engine::{LtpConfig, LtpEngine, RedPart};
+    use crate::ltp::segment::Segment;
+    use crate::packet::{PacketData, PacketSize};
+
+    #[test]
+    fn test_red_retransmission() {
+        let config = LtpConfig {
+            red_part: RedPart::Header,
+            ..LtpConfig::default()
+        };
+        let mut sender = LtpEngine::new(config.clone());
+        let mut receiver = LtpEngine::new(config);
+
+        let mut packet = PacketData::raw((0..20).collect::<Vec<u8>>().into_boxed_slice());
+        packet.packet_type = Some("Test");
+        packet.size = PacketSize::new(8, 12);
+
+        let actions = sender.send_block(0, Some(1), packet.clone(), 4, 10);
+        assert_eq!(actions.segments.len(), 5);
+        assert_eq!(actions.timers.len(), 1);
+
+        let mut segments = actions.segments.into_iter();
+        let _lost = segments.next();
+
+        let mut reports = vec![];
+        for segment in segments {
+            let actions = receiver.on_segment(1, segment, 0);
+            assert!(actions.blocks.is_empty());
+            reports.extend(actions.segments);
+        }
+        assert_eq!(reports.len(), 1);
+
+        let actions = sender.on_segment(0, reports.remove(0), 0);
+        assert_eq!(actions.segments.len(), 1);
+        assert!(matches!(actions.segments[0], Segment::Data { checkpoint_serial: Some(1), .. }));
+
+        let actions = receiver.on_segment(1, actions.segments[0].clone(), 0);
+        assert_eq!(actions.blocks.len(), 1);
+        assert_eq!(actions.blocks[0].data, packet.data);
+        assert_eq!(actions.blocks[0].packet_type, Some("Test"));
+        assert_eq!(actions.blocks[0].size, packet.size);
+
+        sender.on_segment(0, actions.segments[0].clone(), 0);
+        assert_eq!(sender.export_session_count(), 0);
+    }
+
+    #[test]
+    fn test_lost_checkpoint_resent_on_timer() {
+        let mut sender = LtpEngine::new(LtpConfig::default());
+        let mut receiver = LtpEngine::new(LtpConfig::default());
+
+        let packet = PacketData::raw((0..12).collect::<Vec<u8>>().into_boxed_slice());
+
+        let actions = sender.send_block(0, Some(1), packet.clone(), 4, 10);
+        assert_eq!(actions.segments.len(), 3);
+        let (session_number, checkpoint_serial, _) = actions.timers[0];
+
+        let mut segments = actions.segments;
+        let _lost_checkpoint = segments.pop();
+        for segment in segments {
+            let actions = receiver.on_segment(1, segment, 0);
+            assert!(actions.segments.is_empty());
+            assert!(actions.blocks.is_empty());
+        }
+
+        // The nodes have moved apart since the block was sent.
+        let actions = sender.on_timer(session_number, checkpoint_serial, |_| 30);
+        assert_eq!(actions.segments.len(), 1);
+        assert_eq!(actions.timers[0].2, 30 + LtpConfig::default().report_timeout_margin);
+
+        let actions = receiver.on_segment(1, actions.segments[0].clone(), 0);
+        assert_eq!(actions.blocks.len(), 1);
+        assert_eq!(actions.blocks[0].data, packet.data);
+
+        sender.on_segment(0, actions.segments[0].clone(), 0);
+        assert_eq!(sender.export_session_count(), 0);
+    }
+}
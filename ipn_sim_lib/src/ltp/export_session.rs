@@ -0,0 +1,55 @@
+use crate::ltp::segment::{Range, Segment};
+use crate::utils::{NodeId, TimeMetric};
+
+/// The sending side of a block with a red part, kept until the receiver reports all of it.
+#[derive(Clone)]
+pub struct ExportSession {
+    /// Every segment of the block, as first transmitted.
+    pub segments: Vec<Segment>,
+    pub red_length: usize,
+    pub destination_id: NodeId,
+    /// Round trip light time to the destination when a report timer was last started.
+    pub round_trip_time: TimeMetric,
+    pub checkpoint_serial: u32,
+    pub retransmissions: u8,
+}
+
+impl ExportSession {
+    /// Red segments not fully covered by the claims, the last marked as a new checkpoint.
+    pub fn unclaimed_red_segments(&mut self, claims: &[Range]) -> Vec<Segment> {
+        let mut unclaimed = self.segments
+            .iter()
+            .filter(|segment| match segment {
+                Segment::Data { range, .. } => range.offset < self.red_length && !claims
+                    .iter()
+                    .any(|claim| claim.offset <= range.offset && range.end() <= claim.end()),
+                _ => false,
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !unclaimed.is_empty() {
+            self.checkpoint_serial += 1;
+            self.mark_checkpoint(unclaimed.last_mut().unwrap());
+        }
+        unclaimed
+    }
+
+    /// The last red segment, resent as a new checkpoint when a report is overdue.
+    pub fn checkpoint(&mut self) -> Segment {
+        self.checkpoint_serial += 1;
+        let mut checkpoint = self.segments
+            .iter()
+            .rfind(|segment| matches!(segment, Segment::Data { range, .. } if range.offset < self.red_length))
+            .unwrap()
+            .clone();
+        self.mark_checkpoint(&mut checkpoint);
+        checkpoint
+    }
+
+    fn mark_checkpoint(&self, segment: &mut Segment) {
+        if let Segment::Data { checkpoint_serial, .. } = segment {
+            *checkpoint_serial = Some(self.checkpoint_serial);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use crate::ltp::segment::Range;
+use crate::packet::{PacketData, PacketSize};
+use crate::utils::{Data, TimeMetric};
+
+/// The receiving side of a block, collecting segments until the whole block has arrived.
+#[derive(Clone)]
+pub struct ImportSession {
+    pub block_length: usize,
+    pub red_length: usize,
+    pub block_type: Option<&'static str>,
+    pub block_size: PacketSize,
+    pub last_activity: TimeMetric,
+    pieces: BTreeMap<usize, (Range, Data)>,
+}
+
+impl ImportSession {
+    pub fn new(
+        block_length: usize,
+        red_length: usize,
+        block_type: Option<&'static str>,
+        block_size: PacketSize,
+        time: TimeMetric,
+    ) -> Self {
+        Self {
+            block_length,
+            red_length,
+            block_type,
+            block_size,
+            last_activity: time,
+            pieces: BTreeMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, range: Range, data: Data, time: TimeMetric) {
+        self.last_activity = time;
+        self.pieces.insert(range.offset, (range, data));
+    }
+
+    /// Contiguous ranges received, merged.
+    pub fn received(&self) -> Vec<Range> {
+        let mut received: Vec<Range> = vec![];
+        for (range, _) in self.pieces.values() {
+            match received.last_mut() {
+                Some(last) if last.end() >= range.offset => {
+                    last.length = last.length.max(range.end() - last.offset);
+                }
+                _ => received.push(*range),
+            }
+        }
+        received
+    }
+
+    /// Received ranges within the red part, as claimed in reports.
+    pub fn red_claims(&self) -> Vec<Range> {
+        self.received()
+            .into_iter()
+            .filter(|range| range.offset < self.red_length)
+            .map(|range| Range {
+                offset: range.offset,
+                length: range.end().min(self.red_length) - range.offset,
+            })
+            .collect()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        let received = self.received();
+        received.len() == 1 && received[0].offset == 0 && received[0].length >= self.block_length
+            || self.block_length == 0 && !self.pieces.is_empty()
+    }
+
+    /// The block, once complete. Its size is only split into header and payload as sent if that
+    /// agrees with the block length.
+    pub fn into_block(self) -> PacketData {
+        let mut block = PacketData::raw(self.pieces
+            .into_iter()
+            .flat_map(|(_, (_, data))| data.into_vec())
+            .collect());
+        block.packet_type = self.block_type;
+        block.size = if self.block_size.total() == self.block_length {
+            self.block_size
+        } else {
+            PacketSize::header(self.block_length)
+        };
+        block
+    }
+}
@@ -0,0 +1,4 @@
+pub mod segment;
+pub mod export_session;
+pub mod import_session;
+pub mod engine;
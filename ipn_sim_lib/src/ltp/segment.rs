@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use crate::binary_serde::BinarySerde;
+use crate::packet::{Packet, PacketData, PacketSize};
+use crate::utils::{Data, NodeId};
+use crate::wire_size::{WireSize, TAG_SIZE};
+
+/// Nominal size of the offset and length fields, encoded as SDNVs on the wire.
+const SDNV_SIZE: usize = 4;
+
+/// A range of a block, in nominal bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    pub offset: usize,
+    pub length: usize,
+}
+
+impl Range {
+    pub fn end(&self) -> usize {
+        self.offset + self.length
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Segment {
+    Data {
+        source_id: NodeId,
+        /// `None` for green blocks broadcast to every node in range.
+        destination_id: Option<NodeId>,
+        session_number: u32,
+        range: Range,
+        block_length: usize,
+        red_length: usize,
+        /// Set on the last red segment, asking the receiver for a report.
+        checkpoint_serial: Option<u32>,
+        /// The bytes of the block's encoding corresponding to the range.
+        data: Data,
+        /// Type and size of the packet carried by the block. The simulation hands them to the
+        /// receiver along with the segment rather than encoding them.
+        #[serde(skip)]
+        block_type: Option<&'static str>,
+        #[serde(skip)]
+        block_size: PacketSize,
+    },
+    /// The red ranges received by `source_id`, in response to a checkpoint.
+    Report {
+        source_id: NodeId,
+        destination_id: NodeId,
+        session_number: u32,
+        checkpoint_serial: u32,
+        claims: Vec<Range>,
+    },
+}
+
+impl Segment {
+    /// Whether a packet carries an LTP segment rather than a router packet.
+    pub fn is_segment(packet: &PacketData) -> bool {
+        matches!(packet.packet_type, Some("LtpData") | Some("LtpReport"))
+    }
+
+    /// Nominal size of a data segment header with a destination and a checkpoint, the largest
+    /// there is.
+    pub fn max_data_header_size() -> usize {
+        Segment::Data {
+            source_id: 0,
+            destination_id: Some(0),
+            session_number: 0,
+            range: Range { offset: 0, length: 0 },
+            block_length: 0,
+            red_length: 0,
+            checkpoint_serial: Some(0),
+            data: Data::default(),
+            block_type: None,
+            block_size: PacketSize::default(),
+        }.packet_size().unwrap().header
+    }
+}
+
+impl BinarySerde for Segment {}
+
+impl Packet for Segment {
+    /// Shared so receivers get the block type and size, which are not encoded.
+    const SHARED: bool = true;
+
+    fn packet_type(&self) -> &'static str {
+        match self {
+            Segment::Data { .. } => "LtpData",
+            Segment::Report { .. } => "LtpReport",
+        }
+    }
+
+    fn packet_size(&self) -> Option<PacketSize> {
+        Some(match self {
+            Segment::Data { source_id, destination_id, session_number, range, checkpoint_serial, .. } => PacketSize::new(
+                TAG_SIZE
+                    + source_id.wire_size()
+                    + destination_id.wire_size()
+                    + session_number.wire_size()
+                    + 2 * SDNV_SIZE
+                    + checkpoint_serial.wire_size(),
+                range.length,
+            ),
+            Segment::Report { source_id, destination_id, session_number, checkpoint_serial, claims } => PacketSize::header(
+                TAG_SIZE
+                    + source_id.wire_size()
+                    + destination_id.wire_size()
+                    + session_number.wire_size()
+                    + checkpoint_serial.wire_size()
+                    + (1 + 2 * claims.len()) * SDNV_SIZE
+            ),
+        })
+    }
+}
@@ -1,5 +1,6 @@
-use crate::utils::{NodeId, Shared};
+use crate::utils::{GroupId, NodeId, Shared};
 use crate::node::node::Node;
+use crate::ipn_sim::groups::Groups;
 use std::rc::Rc;
 use serde::{Deserialize, Serialize};
 
@@ -8,30 +9,65 @@ pub enum MessageDestination<T> {
     All,
     Single(T),
     Multiple(Vec<T>),
+    /// Every node that is a member of the group when the message reaches it.
+    Multicast(GroupId),
+    /// Any one member of the group.
+    Anycast(GroupId),
 }
 
+impl<T> MessageDestination<T> {
+    pub fn group_id(&self) -> Option<GroupId> {
+        match self {
+            MessageDestination::Multicast(group_id) | MessageDestination::Anycast(group_id) => Some(*group_id),
+            _ => None,
+        }
+    }
+
+    pub fn is_anycast(&self) -> bool {
+        matches!(self, MessageDestination::Anycast(_))
+    }
+}
+
+/// Whether a node is a destination, resolving groups by their current membership.
 pub trait IsIncluded<T> {
-    fn is_included(&self, other: T) -> bool;
+    fn is_included(&self, other: T, groups: &Groups) -> bool;
 }
 
 impl IsIncluded<&NodeId> for MessageDestination<NodeId> {
-    fn is_included(&self, other: &NodeId) -> bool {
+    fn is_included(&self, other: &NodeId, groups: &Groups) -> bool {
         match self {
             MessageDestination::All => true,
             MessageDestination::Single(id) => *id == *other,
             MessageDestination::Multiple(ids) => ids.contains(other),
+            MessageDestination::Multicast(group_id) | MessageDestination::Anycast(group_id) =>
+                groups.is_member(*group_id, *other),
         }
     }
 }
 
 impl IsIncluded<&Shared<Node>> for MessageDestination<Shared<Node>> {
-    fn is_included(&self, other: &Shared<Node>) -> bool {
+    fn is_included(&self, other: &Shared<Node>, groups: &Groups) -> bool {
         match self {
             MessageDestination::All => true,
             MessageDestination::Single(node) => Rc::ptr_eq(node, other),
             MessageDestination::Multiple(nodes) => nodes
                 .iter()
                 .any(|node| Rc::ptr_eq(node, other)),
+            MessageDestination::Multicast(group_id) | MessageDestination::Anycast(group_id) =>
+                groups.is_member(*group_id, other.borrow().id),
         }
     }
-}
\ No newline at end of file
+}
+
+impl MessageDestination<NodeId> {
+    /// The destination node ids, resolving groups by their current membership, or `None` if the
+    /// message is destined to every node.
+    pub fn node_ids(&self, groups: &Groups) -> Option<Vec<NodeId>> {
+        match self {
+            MessageDestination::All => None,
+            MessageDestination::Single(id) => Some(vec![*id]),
+            MessageDestination::Multiple(ids) => Some(ids.clone()),
+            MessageDestination::Multicast(group_id) | MessageDestination::Anycast(group_id) => Some(groups.members(*group_id)),
+        }
+    }
+}
@@ -1,13 +1,23 @@
-use cgmath::Point3;
+use cgmath::{Point3, Vector3};
 use downcast_rs::{impl_downcast, Downcast};
 use dyn_clonable::clonable;
 
 use crate::utils::{SpaceMetric, TimeMetric};
 use std::hash::Hash;
 
+/// Time between the positions used to estimate velocities.
+const VELOCITY_STEP: TimeMetric = 1_000_000_000;
+
 #[clonable]
 pub trait Movement: Clone + Downcast {
     fn get_position_at(&self, time: TimeMetric) -> Point3<SpaceMetric>;
+
+    /// Velocity in metres per second, by default a central difference of positions a second apart.
+    fn get_velocity_at(&self, time: TimeMetric) -> Vector3<SpaceMetric> {
+        let before = time.saturating_sub(VELOCITY_STEP / 2);
+        let after = before + VELOCITY_STEP;
+        (self.get_position_at(after) - self.get_position_at(before)) / (VELOCITY_STEP as SpaceMetric / 1e9)
+    }
 }
 
 impl_downcast!(Movement);
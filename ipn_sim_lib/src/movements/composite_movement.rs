@@ -0,0 +1,121 @@
+use std::fmt;
+
+use cgmath::{InnerSpace, MetricSpace, Point3, Vector3};
+
+use crate::movement::Movement;
+use crate::utils::{SpaceMetric, TimeMetric};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContinuityError {
+    /// Index of the phase that starts with the discontinuity.
+    pub phase: usize,
+    pub time: TimeMetric,
+    /// Metres.
+    pub position_gap: SpaceMetric,
+    /// Metres per second.
+    pub velocity_gap: SpaceMetric,
+}
+
+impl fmt::Display for ContinuityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "phase {} starting at {}ns jumps {}m and {}m/s",
+            self.phase, self.time, self.position_gap, self.velocity_gap
+        )
+    }
+}
+
+impl std::error::Error for ContinuityError {}
+
+/// Switches between movements at scheduled times, such as an orbit followed by a transfer and a
+/// new orbit. Each movement is evaluated at the sim time, not the time since its phase started.
+#[derive(Clone)]
+pub struct CompositeMovement {
+    phases: Vec<(TimeMetric, Box<dyn Movement>)>,
+}
+
+impl CompositeMovement {
+    pub fn new(movement: impl Movement + 'static) -> Self {
+        Self {
+            phases: vec![(0, Box::new(movement))],
+        }
+    }
+
+    /// Switches to `movement` from `start` onwards. Phases must be added in order of start time.
+    pub fn then(mut self, start: TimeMetric, movement: impl Movement + 'static) -> Self {
+        assert!(start > self.phases.last().unwrap().0, "phases must start in order");
+        self.phases.push((start, Box::new(movement)));
+        self
+    }
+
+    /// Checks that the position and velocity change by no more than the given gaps when each
+    /// phase starts.
+    pub fn check_continuity(
+        &self,
+        max_position_gap: SpaceMetric,
+        max_velocity_gap: SpaceMetric,
+    ) -> Result<(), ContinuityError> {
+        for (phase, window) in self.phases.windows(2).enumerate() {
+            let (_, previous) = &window[0];
+            let (time, next) = &window[1];
+            let error = ContinuityError {
+                phase: phase + 1,
+                time: *time,
+                position_gap: previous.get_position_at(*time).distance(next.get_position_at(*time)),
+                velocity_gap: (previous.get_velocity_at(*time) - next.get_velocity_at(*time)).magnitude(),
+            };
+            if error.position_gap > max_position_gap || error.velocity_gap > max_velocity_gap {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    fn movement_at(&self, time: TimeMetric) -> &dyn Movement {
+        let index = self.phases.partition_point(|(start, _)| *start <= time);
+        self.phases[index - 1].1.as_ref()
+    }
+}
+
+impl Movement for CompositeMovement {
+    fn get_position_at(&self, time: TimeMetric) -> Point3<SpaceMetric> {
+        self.movement_at(time).get_position_at(time)
+    }
+
+    fn get_velocity_at(&self, time: TimeMetric) -> Vector3<SpaceMetric> {
+        self.movement_at(time).get_velocity_at(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Point3, Vector3};
+
+    use crate::movement::Movement;
+    use crate::movements::composite_movement::CompositeMovement;
+    use crate::movements::free_flight_movement::FreeFlightMovement;
+    use crate::movements::path_movement::PathMovement;
+    use crate::movements::static_movement::StaticMovement;
+
+    #[test]
+    fn test_composite_movement() {
+        let second = 1_000_000_000;
+        let movement = CompositeMovement::new(StaticMovement::new(Point3::new(0., 0., 0.)))
+            .then(10 * second, PathMovement::new(vec![
+                (10 * second, Point3::new(0., 0., 0.)),
+                (20 * second, Point3::new(100., 0., 0.)),
+            ]))
+            .then(20 * second, FreeFlightMovement::new(Point3::new(-100., 0., 0.), Vector3::new(10., 0., 0.)));
+
+        assert_eq!(movement.get_position_at(5 * second), Point3::new(0., 0., 0.));
+        assert_eq!(movement.get_position_at(15 * second), Point3::new(50., 0., 0.));
+        assert_eq!(movement.get_velocity_at(15 * second), Vector3::new(10., 0., 0.));
+        assert_eq!(movement.get_position_at(30 * second), Point3::new(200., 0., 0.));
+
+        // The path starts moving abruptly, but without jumping in position.
+        let error = movement.check_continuity(1e-9, 1.).unwrap_err();
+        assert_eq!((error.phase, error.position_gap, error.velocity_gap), (1, 0., 10.));
+        assert!(movement.check_continuity(1e-9, 10.).is_ok());
+    }
+}
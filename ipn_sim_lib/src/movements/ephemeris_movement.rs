@@ -0,0 +1,433 @@
+use std::fmt;
+use std::rc::Rc;
+
+use cgmath::{EuclideanSpace, Point3, Vector3, VectorSpace, Zero};
+
+use crate::body::Body;
+use crate::ipn_sim::epoch::Epoch;
+use crate::movement::Movement;
+use crate::utils::{Shared, SpaceMetric, TimeMetric};
+
+const NANOSECONDS_PER_DAY: SpaceMetric = 1_000_000_000. * 86_400.;
+
+/// A time-tagged state. `time` is in nanoseconds from the start of the simulation and may be
+/// negative, `velocity` is in metres per second.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EphemerisRecord {
+    pub time: SpaceMetric,
+    pub position: Point3<SpaceMetric>,
+    pub velocity: Option<Vector3<SpaceMetric>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interpolation {
+    Linear,
+    /// Lagrange polynomial through this many records around the requested time.
+    Lagrange(usize),
+    /// Cubic Hermite spline through the records' positions and velocities, estimating velocities
+    /// by finite differences where a record has none.
+    Hermite,
+}
+
+/// Metres per unit of length and metres per second per unit of velocity in a table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EphemerisUnits {
+    pub length: SpaceMetric,
+    pub velocity: SpaceMetric,
+}
+
+impl EphemerisUnits {
+    pub const METRES: EphemerisUnits = EphemerisUnits { length: 1., velocity: 1. };
+    pub const KILOMETRES: EphemerisUnits = EphemerisUnits { length: 1000., velocity: 1000. };
+    pub const ASTRONOMICAL_UNITS_PER_DAY: EphemerisUnits = EphemerisUnits {
+        length: 1.495_978_707e11,
+        velocity: 1.495_978_707e11 / 86_400.,
+    };
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EphemerisError {
+    Row { line: usize },
+    Unsorted { line: usize },
+    Duplicate { line: usize },
+    Empty,
+}
+
+impl fmt::Display for EphemerisError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EphemerisError::Row { line } => write!(f, "line {} is not a valid ephemeris record", line),
+            EphemerisError::Unsorted { line } => write!(f, "record on line {} is earlier than the one before", line),
+            EphemerisError::Duplicate { line } => write!(f, "record on line {} has the same time as the one before", line),
+            EphemerisError::Empty => write!(f, "ephemeris has no records"),
+        }
+    }
+}
+
+impl std::error::Error for EphemerisError {}
+
+/// Movement along a table of states exported from an ephemeris tool, optionally relative to a
+/// central body.
+#[derive(Clone)]
+pub struct EphemerisMovement {
+    records: Vec<EphemerisRecord>,
+    pub interpolation: Interpolation,
+    central_body: Option<Shared<Body>>,
+}
+
+impl EphemerisMovement {
+    /// Panics if there are no records, or if their times are not finite and distinct.
+    pub fn new(mut records: Vec<EphemerisRecord>, interpolation: Interpolation) -> Self {
+        assert!(!records.is_empty(), "ephemeris has no records");
+        assert!(records.iter().all(|record| record.time.is_finite()), "ephemeris record times must be finite");
+        records.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+        assert!(
+            records.windows(2).all(|pair| pair[0].time < pair[1].time),
+            "ephemeris record times must be distinct",
+        );
+        Self {
+            records,
+            interpolation,
+            central_body: None,
+        }
+    }
+
+    /// Reads rows of `julian date, x, y, z[, vx, vy, vz, ...]`, skipping headers, `#` comments
+    /// and non-numeric columns such as calendar dates. If the text has `$$SOE` and `$$EOE`
+    /// markers, only the rows between them are read.
+    pub fn from_csv(
+        text: &str,
+        epoch: Epoch,
+        units: EphemerisUnits,
+        interpolation: Interpolation,
+    ) -> Result<Self, EphemerisError> {
+        let mut records = vec![];
+        for (line, row) in table_lines(text) {
+            if row.starts_with('#') {
+                continue;
+            }
+            let values = row
+                .split(',')
+                .filter_map(|column| column.trim().parse::<SpaceMetric>().ok())
+                .collect::<Vec<_>>();
+            match values.len() {
+                0 => continue,
+                1..=3 => return Err(EphemerisError::Row { line }),
+                _ => push_record(&mut records, line, epoch, units, values[0], &values[1..4], values.get(4..7))?,
+            }
+        }
+        Self::from_records(records, interpolation)
+    }
+
+    /// Reads the vector table of a JPL Horizons text export, a Julian date line followed by
+    /// `X = .. Y = .. Z = ..` and optionally `VX= .. VY= .. VZ= ..`. TDB dates are read as UTC,
+    /// ignoring their offset of about a minute.
+    pub fn from_horizons(
+        text: &str,
+        epoch: Epoch,
+        units: EphemerisUnits,
+        interpolation: Interpolation,
+    ) -> Result<Self, EphemerisError> {
+        let mut records = vec![];
+        let mut current: Option<(usize, SpaceMetric, String)> = None;
+        for (line, row) in table_lines(text).chain(std::iter::once((0, "0"))) {
+            let julian_date = row
+                .split_whitespace()
+                .next()
+                .and_then(|token| token.parse::<SpaceMetric>().ok());
+            match julian_date {
+                Some(julian_date) => {
+                    if let Some((line, julian_date, values)) = current.take() {
+                        let value = |key: &str| horizons_value(&values, key);
+                        let position = [value("X"), value("Y"), value("Z")];
+                        if position.iter().any(Option::is_none) {
+                            return Err(EphemerisError::Row { line });
+                        }
+                        let velocity = [value("VX"), value("VY"), value("VZ")];
+                        let velocity = velocity.iter().copied().collect::<Option<Vec<_>>>();
+                        push_record(
+                            &mut records,
+                            line,
+                            epoch,
+                            units,
+                            julian_date,
+                            &position.iter().map(|value| value.unwrap()).collect::<Vec<_>>(),
+                            velocity.as_deref(),
+                        )?;
+                    }
+                    current = Some((line, julian_date, String::new()));
+                }
+                None => match current.as_mut() {
+                    Some((_, _, values)) => {
+                        values.push(' ');
+                        values.push_str(row);
+                    }
+                    None => return Err(EphemerisError::Row { line }),
+                },
+            }
+        }
+        Self::from_records(records, interpolation)
+    }
+
+    fn from_records(records: Vec<EphemerisRecord>, interpolation: Interpolation) -> Result<Self, EphemerisError> {
+        if records.is_empty() {
+            return Err(EphemerisError::Empty);
+        }
+        Ok(Self::new(records, interpolation))
+    }
+
+    /// Makes the table positions relative to a moving body.
+    pub fn relative_to(mut self, central_body: &Shared<Body>) -> Self {
+        self.central_body = Some(Rc::clone(central_body));
+        self
+    }
+
+    pub fn records(&self) -> &[EphemerisRecord] {
+        &self.records
+    }
+
+    /// Index of the last record at or before `time`, or `None` outside the table.
+    fn segment(&self, time: SpaceMetric) -> Option<usize> {
+        let index = self.records.partition_point(|record| record.time <= time);
+        if index == 0 || index == self.records.len() {
+            None
+        } else {
+            Some(index - 1)
+        }
+    }
+
+    fn interpolate(&self, time: SpaceMetric, index: usize) -> (Vector3<SpaceMetric>, Vector3<SpaceMetric>) {
+        let (a, b) = (&self.records[index], &self.records[index + 1]);
+        let step = (b.time - a.time) / 1e9;
+        let s = (time - a.time) / (b.time - a.time);
+
+        match self.interpolation {
+            Interpolation::Linear => (
+                a.position.to_vec().lerp(b.position.to_vec(), s),
+                (b.position - a.position) / step,
+            ),
+            Interpolation::Lagrange(points) => {
+                let points = points.max(2).min(self.records.len());
+                let start = (index + 1).saturating_sub(points / 2).min(self.records.len() - points);
+                lagrange(&self.records[start..start + points], time)
+            }
+            Interpolation::Hermite => {
+                let (v0, v1) = (self.velocity_estimate(index), self.velocity_estimate(index + 1));
+                let (p0, p1) = (a.position.to_vec(), b.position.to_vec());
+                let (s2, s3) = (s * s, s * s * s);
+                (
+                    p0 * (2. * s3 - 3. * s2 + 1.) + v0 * (step * (s3 - 2. * s2 + s))
+                        + p1 * (3. * s2 - 2. * s3) + v1 * (step * (s3 - s2)),
+                    (p1 - p0) * ((6. * s - 6. * s2) / step) + v0 * (3. * s2 - 4. * s + 1.) + v1 * (3. * s2 - 2. * s),
+                )
+            }
+        }
+    }
+
+    fn velocity_estimate(&self, index: usize) -> Vector3<SpaceMetric> {
+        if let Some(velocity) = self.records[index].velocity {
+            return velocity;
+        }
+        let before = &self.records[index.saturating_sub(1)];
+        let after = &self.records[(index + 1).min(self.records.len() - 1)];
+        (after.position - before.position) / ((after.time - before.time) / 1e9)
+    }
+}
+
+impl Movement for EphemerisMovement {
+    fn get_position_at(&self, time: TimeMetric) -> Point3<SpaceMetric> {
+        let position = match self.segment(time as SpaceMetric) {
+            Some(index) => self.interpolate(time as SpaceMetric, index).0,
+            None if (time as SpaceMetric) < self.records[0].time => self.records[0].position.to_vec(),
+            None => self.records.last().unwrap().position.to_vec(),
+        };
+        match &self.central_body {
+            Some(central_body) => central_body.borrow().movement.get_position_at(time) + position,
+            None => Point3::from_vec(position),
+        }
+    }
+
+    /// Zero relative to the central body outside the table.
+    fn get_velocity_at(&self, time: TimeMetric) -> Vector3<SpaceMetric> {
+        let velocity = match self.segment(time as SpaceMetric) {
+            Some(index) => self.interpolate(time as SpaceMetric, index).1,
+            None => Vector3::zero(),
+        };
+        match &self.central_body {
+            Some(central_body) => central_body.borrow().movement.get_velocity_at(time) + velocity,
+            None => velocity,
+        }
+    }
+}
+
+/// Position and velocity of the Lagrange polynomial through `records` at `time`.
+fn lagrange(records: &[EphemerisRecord], time: SpaceMetric) -> (Vector3<SpaceMetric>, Vector3<SpaceMetric>) {
+    // Seconds relative to the requested time keep the products well scaled.
+    let times = records
+        .iter()
+        .map(|record| (record.time - time) / 1e9)
+        .collect::<Vec<_>>();
+
+    let mut position = Vector3::zero();
+    let mut velocity = Vector3::zero();
+    for (j, record) in records.iter().enumerate() {
+        let basis = (0..times.len())
+            .filter(|&m| m != j)
+            .map(|m| -times[m] / (times[j] - times[m]))
+            .product::<SpaceMetric>();
+        let derivative = (0..times.len())
+            .filter(|&i| i != j)
+            .map(|i| {
+                (0..times.len())
+                    .filter(|&m| m != i && m != j)
+                    .map(|m| -times[m] / (times[j] - times[m]))
+                    .product::<SpaceMetric>() / (times[j] - times[i])
+            })
+            .sum::<SpaceMetric>();
+        position += record.position.to_vec() * basis;
+        velocity += record.position.to_vec() * derivative;
+    }
+    (position, velocity)
+}
+
+/// Lines of the table body, numbered from 1, between `$$SOE` and `$$EOE` if present.
+fn table_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let marked = text.contains("$$SOE");
+    let mut inside = !marked;
+    text.lines()
+        .enumerate()
+        .filter_map(move |(index, line)| {
+            let line = line.trim();
+            match line {
+                "$$SOE" => inside = true,
+                "$$EOE" => inside = false,
+                _ if inside && !line.is_empty() => return Some((index + 1, line)),
+                _ => {}
+            }
+            None
+        })
+}
+
+fn horizons_value(values: &str, key: &str) -> Option<SpaceMetric> {
+    let values = values.replace('=', " = ");
+    let tokens = values.split_whitespace().collect::<Vec<_>>();
+    tokens
+        .windows(3)
+        .find(|window| window[0] == key && window[1] == "=")
+        .and_then(|window| window[2].parse().ok())
+}
+
+/// Ephemeris tools export with z towards the pole, which is y in the sim frame.
+fn to_sim_frame(x: SpaceMetric, y: SpaceMetric, z: SpaceMetric) -> Vector3<SpaceMetric> {
+    Vector3::new(x, z, -y)
+}
+
+fn push_record(
+    records: &mut Vec<EphemerisRecord>,
+    line: usize,
+    epoch: Epoch,
+    units: EphemerisUnits,
+    julian_date: SpaceMetric,
+    position: &[SpaceMetric],
+    velocity: Option<&[SpaceMetric]>,
+) -> Result<(), EphemerisError> {
+    let time = (julian_date - epoch.julian_date) * NANOSECONDS_PER_DAY;
+    if !time.is_finite() {
+        return Err(EphemerisError::Row { line });
+    }
+    match records.last() {
+        Some(last) if last.time > time => return Err(EphemerisError::Unsorted { line }),
+        Some(last) if last.time == time => return Err(EphemerisError::Duplicate { line }),
+        _ => {}
+    }
+    records.push(EphemerisRecord {
+        time,
+        position: Point3::from_vec(to_sim_frame(position[0], position[1], position[2]) * units.length),
+        velocity: velocity.map(|velocity| to_sim_frame(velocity[0], velocity[1], velocity[2]) * units.velocity),
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use cgmath::{EuclideanSpace, InnerSpace, MetricSpace, Point3, Vector3};
+
+    use crate::ipn_sim::epoch::Epoch;
+    use crate::movement::Movement;
+    use crate::movements::ephemeris_movement::{EphemerisError, EphemerisMovement, EphemerisRecord, EphemerisUnits, Interpolation};
+
+    #[test]
+    fn test_interpolation() {
+        let radius = 1e7;
+        let rate = 2. * PI / 86_400.;
+        let position = |seconds: f64| Point3::new(radius * (rate * seconds).cos(), 0., radius * (rate * seconds).sin());
+        let records = (0..=24)
+            .map(|hour| {
+                let seconds = hour as f64 * 3600.;
+                EphemerisRecord {
+                    time: seconds * 1e9,
+                    position: position(seconds),
+                    velocity: Some(Vector3::new(-(rate * seconds).sin(), 0., (rate * seconds).cos()) * radius * rate),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for &(interpolation, tolerance) in &[
+            (Interpolation::Linear, 1e5),
+            (Interpolation::Hermite, 200.),
+            (Interpolation::Lagrange(8), 10.),
+        ] {
+            let movement = EphemerisMovement::new(records.clone(), interpolation);
+            for half_hour in (1..48).step_by(2) {
+                let seconds = half_hour as f64 * 1800.;
+                let time = seconds as u64 * 1_000_000_000;
+                assert!(movement.get_position_at(time).distance(position(seconds)) < tolerance);
+                assert!((movement.get_velocity_at(time).magnitude() - radius * rate).abs() < tolerance * rate * 10.);
+            }
+        }
+
+        let movement = EphemerisMovement::new(records[3..].to_vec(), Interpolation::Hermite);
+        assert_eq!(movement.get_position_at(0), records[3].position);
+    }
+
+    #[test]
+    fn test_horizons_export() {
+        let text = "\
+*******************************************************************************
+$$SOE
+2451545.000000000 = A.D. 2000-Jan-01 12:00:00.0000 TDB
+ X =-2.649903375682292E+07 Y = 1.327574173383451E+08 Z = 5.755671839918014E+07
+ VX=-2.979426006719171E+01 VY=-5.018052326235948E+00 VZ= 1.162884302626914E-04
+2451546.000000000 = A.D. 2000-Jan-02 12:00:00.0000 TDB
+ X =-2.907084067014441E+07 Y = 1.323176541393939E+08 Z = 5.736636453693003E+07
+ VX=-2.973434007883408E+01 VY=-5.473733564478563E+00 VZ=-1.980225498052932E-04
+$$EOE
+*******************************************************************************";
+        let movement = EphemerisMovement::from_horizons(
+            text,
+            Epoch::J2000,
+            EphemerisUnits::KILOMETRES,
+            Interpolation::Hermite,
+        ).unwrap();
+        assert_eq!(movement.records().len(), 2);
+        assert_eq!(movement.records()[1].time, 86_400e9);
+        assert!((movement.get_position_at(0).to_vec() - Vector3::new(-2.649903375682292e10, 5.755671839918014e10, -1.327574173383451e11)).magnitude() < 1e-3);
+
+        let csv = "JDTDB, Calendar Date (TDB), X, Y, Z,\n\
+            2451545.0, A.D. 2000-Jan-01 12:00:00.0000, 1.0, 2.0, 3.0,\n\
+            2451546.0, A.D. 2000-Jan-02 12:00:00.0000, 3.0, 2.0, 1.0,\n";
+        let movement = EphemerisMovement::from_csv(csv, Epoch::J2000, EphemerisUnits::METRES, Interpolation::Linear).unwrap();
+        assert_eq!(movement.get_position_at(43_200_000_000_000), Point3::new(2., 2., -2.));
+    }
+
+    #[test]
+    fn test_invalid_sample_times() {
+        let load = |csv: &str| EphemerisMovement::from_csv(csv, Epoch::J2000, EphemerisUnits::METRES, Interpolation::Linear).err();
+
+        assert_eq!(load("2451545.0, 1.0, 2.0, 3.0\nNaN, 1.0, 2.0, 3.0\n"), Some(EphemerisError::Row { line: 2 }));
+        assert_eq!(load("2451545.0, 1.0, 2.0, 3.0\n2451545.0, 3.0, 2.0, 1.0\n"), Some(EphemerisError::Duplicate { line: 2 }));
+        assert_eq!(load("2451546.0, 1.0, 2.0, 3.0\n2451545.0, 3.0, 2.0, 1.0\n"), Some(EphemerisError::Unsorted { line: 2 }));
+    }
+}
@@ -0,0 +1,29 @@
+use cgmath::{Point3, Vector3};
+
+use crate::movement::Movement;
+use crate::utils::{SpaceMetric, TimeMetric};
+
+/// A spacecraft coasting from an initial state, with `velocity` in metres per second. On its own
+/// it moves in a straight line; with N-body propagation enabled on the simulation it follows the
+/// gravity of the bodies instead.
+#[derive(Clone)]
+pub struct FreeFlightMovement {
+    pub position: Point3<SpaceMetric>,
+    pub velocity: Vector3<SpaceMetric>,
+}
+
+impl FreeFlightMovement {
+    pub fn new(position: Point3<SpaceMetric>, velocity: Vector3<SpaceMetric>) -> Self {
+        Self { position, velocity }
+    }
+}
+
+impl Movement for FreeFlightMovement {
+    fn get_position_at(&self, time: TimeMetric) -> Point3<SpaceMetric> {
+        self.position + self.velocity * (time as SpaceMetric / 1e9)
+    }
+
+    fn get_velocity_at(&self, _time: TimeMetric) -> Vector3<SpaceMetric> {
+        self.velocity
+    }
+}
@@ -1,3 +1,13 @@
+pub mod composite_movement;
+pub mod ephemeris_movement;
+pub mod free_flight_movement;
+pub mod n_body;
+pub mod orbital_elements;
 pub mod orbital_movement;
 pub mod path_movement;
+pub mod relative_movement;
+pub mod sgp4;
 pub mod static_movement;
+pub mod surface_movement;
+pub mod tle;
+pub mod tle_movement;
@@ -0,0 +1,182 @@
+use cgmath::{InnerSpace, Point3, Vector3, Zero};
+
+use crate::body::Body;
+use crate::movements::ephemeris_movement::{EphemerisMovement, EphemerisRecord, Interpolation};
+use crate::movements::free_flight_movement::FreeFlightMovement;
+use crate::node::node::Node;
+use crate::utils::{G, Shared, SpaceMetric, TimeMetric};
+
+/// Integrates the bodies under their mutual gravity, and nodes with a [`FreeFlightMovement`] as
+/// massless particles, with a fixed step kick-drift-kick leapfrog. Trajectories are stored as
+/// ephemeris tables, interpolated with Hermite splines, that replace the original movements.
+/// Initial states are taken from the movements at time zero, less the velocity of the bodies'
+/// barycentre, so nodes orbiting a body with an
+/// [`OrbitalMovement`](crate::movements::orbital_movement::OrbitalMovement) keep their orbit
+/// around it as it is propagated.
+#[derive(Clone, Copy, Debug)]
+pub struct NBodyPropagator {
+    pub step: TimeMetric,
+}
+
+impl NBodyPropagator {
+    pub fn new(step: TimeMetric) -> Self {
+        Self { step }
+    }
+
+    pub fn propagate(&self, bodies: &[Shared<Body>], nodes: &[Shared<Node>], length: TimeMetric) {
+        let free_nodes = nodes
+            .iter()
+            .filter_map(|node| {
+                let free_flight = node.borrow().movement.downcast_ref::<FreeFlightMovement>()?.clone();
+                Some((node, free_flight))
+            })
+            .collect::<Vec<_>>();
+
+        let masses = bodies.iter().map(|body| body.borrow().mass).collect::<Vec<_>>();
+        let mut positions = bodies
+            .iter()
+            .map(|body| body.borrow().movement.get_position_at(0))
+            .chain(free_nodes.iter().map(|(_, free_flight)| free_flight.position))
+            .collect::<Vec<_>>();
+        let mut velocities = bodies
+            .iter()
+            .map(|body| body.borrow().movement.get_velocity_at(0))
+            .chain(free_nodes.iter().map(|(_, free_flight)| free_flight.velocity))
+            .collect::<Vec<_>>();
+
+        // Movements built around a fixed central body leave the system with net momentum, which
+        // would make it drift as a whole.
+        let total_mass = masses.iter().sum::<SpaceMetric>();
+        if total_mass > 0. {
+            let momentum = masses
+                .iter()
+                .zip(&velocities)
+                .fold(Vector3::zero(), |sum, (mass, velocity)| sum + velocity * *mass);
+            let barycentre_velocity = momentum / total_mass;
+            for velocity in &mut velocities {
+                *velocity -= barycentre_velocity;
+            }
+        }
+
+        let dt = self.step as SpaceMetric / 1e9;
+        let steps = length.div_ceil(self.step) + 1;
+        let mut tables = vec![vec![]; positions.len()];
+        let mut accelerations = self.accelerations(&masses, &positions);
+
+        for step in 0..=steps {
+            for (index, table) in tables.iter_mut().enumerate() {
+                table.push(EphemerisRecord {
+                    time: (step * self.step) as SpaceMetric,
+                    position: positions[index],
+                    velocity: Some(velocities[index]),
+                });
+            }
+            if step == steps {
+                break;
+            }
+
+            for (velocity, acceleration) in velocities.iter_mut().zip(&accelerations) {
+                *velocity += acceleration * (dt / 2.);
+            }
+            for (position, velocity) in positions.iter_mut().zip(&velocities) {
+                *position += velocity * dt;
+            }
+            accelerations = self.accelerations(&masses, &positions);
+            for (velocity, acceleration) in velocities.iter_mut().zip(&accelerations) {
+                *velocity += acceleration * (dt / 2.);
+            }
+        }
+
+        let mut tables = tables.into_iter();
+        for body in bodies {
+            body.borrow_mut().movement = Box::new(EphemerisMovement::new(tables.next().unwrap(), Interpolation::Hermite));
+        }
+        for (node, _) in free_nodes {
+            node.borrow_mut().movement = Box::new(EphemerisMovement::new(tables.next().unwrap(), Interpolation::Hermite));
+        }
+    }
+
+    /// Accelerations in m/s² of every particle due to the massive ones, which come first.
+    fn accelerations(&self, masses: &[SpaceMetric], positions: &[Point3<SpaceMetric>]) -> Vec<Vector3<SpaceMetric>> {
+        positions
+            .iter()
+            .enumerate()
+            .map(|(index, position)| {
+                masses
+                    .iter()
+                    .zip(positions)
+                    .enumerate()
+                    .filter(|(other_index, _)| *other_index != index)
+                    .map(|(_, (mass, other_position))| {
+                        let offset = other_position - position;
+                        offset * (G * mass / offset.magnitude().powi(3))
+                    })
+                    .fold(Vector3::zero(), |sum, acceleration| sum + acceleration)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{EuclideanSpace, InnerSpace, MetricSpace, Point3, Vector3};
+
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::movements::free_flight_movement::FreeFlightMovement;
+    use crate::movements::orbital_movement::OrbitalMovement;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::node::node_builder::NodeBuilder;
+    use crate::routers::test_router::TestRouter;
+    use crate::solar_system::ASTRONOMICAL_UNIT;
+    use crate::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
+    use crate::utils::G;
+
+    #[test]
+    fn test_n_body_orbits() {
+        let year = 365 * 86_400_000_000_000;
+        let mut builder = IpnSimBuilder::new(year)
+            .add_body("Sun", 1.98847e30, StaticMovement::new(Point3::origin()), 6.957e8);
+        let sun = builder.get_body(0);
+        builder = builder.add_body(
+            "Earth",
+            5.97217e24,
+            OrbitalMovement::new(&sun, ASTRONOMICAL_UNIT, 0., 0., 0., false),
+            6.371e6,
+        );
+
+        // A spacecraft on a circular orbit half as far from the Sun, in the opposite direction.
+        let radius = ASTRONOMICAL_UNIT / 2.;
+        let speed = (G * 1.98847e30 / radius).sqrt();
+        builder = builder.add_node(NodeBuilder::new()
+            .name("spacecraft")
+            .movement(FreeFlightMovement::new(Point3::new(-radius, 0., 0.), Vector3::new(0., 0., speed)))
+            .message_buffer_size(1)
+            .router(TestRouter)
+            .transceive_speed(1.)
+            .transceive_guard(SimpleTransceiveGuard::new(1.)));
+
+        let sim = builder.n_body(3_600_000_000_000).build();
+        let earth = sim.bodies[1].borrow();
+        let spacecraft = sim.get_node(0);
+        for day in 0..=365 {
+            let time = day * 86_400_000_000_000;
+            let sun_position = sim.bodies[0].borrow().movement.get_position_at(time);
+            let earth_distance = earth.movement.get_position_at(time).distance(sun_position);
+            assert!((earth_distance / ASTRONOMICAL_UNIT - 1.).abs() < 1e-3);
+            let spacecraft_distance = spacecraft.borrow().movement.get_position_at(time).distance(sun_position);
+            assert!((spacecraft_distance / radius - 1.).abs() < 1e-3);
+        }
+
+        // A sidereal year later the Earth is back where it started.
+        let sidereal_year = (365.256 * 86_400e9) as u64;
+        let start = earth.movement.get_position_at(0);
+        assert!((earth.movement.get_position_at(sidereal_year) - start).magnitude() / ASTRONOMICAL_UNIT < 0.01);
+
+        // The barycentre stays put rather than drifting with the Sun's initial momentum.
+        let barycentre = |time| {
+            let sun_position = sim.bodies[0].borrow().movement.get_position_at(time).to_vec();
+            (sun_position * 1.98847e30 + earth.movement.get_position_at(time).to_vec() * 5.97217e24) / (1.98847e30 + 5.97217e24)
+        };
+        assert!((barycentre(year) - barycentre(0)).magnitude() < 1e5);
+    }
+}
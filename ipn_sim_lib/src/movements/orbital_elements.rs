@@ -0,0 +1,99 @@
+use std::f64::consts::PI;
+
+use crate::utils::{G, SpaceMetric};
+
+const KEPLER_TOLERANCE: SpaceMetric = 1e-12;
+const KEPLER_MAX_ITERATIONS: usize = 50;
+
+/// Classical orbital elements of an elliptical orbit. Angles are in radians, the ascending node
+/// and argument of periapsis measured as in [`OrbitalMovement`](crate::movements::orbital_movement::OrbitalMovement).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitalElements {
+    pub semimajor_axis: SpaceMetric,
+    pub eccentricity: SpaceMetric,
+    pub inclination: SpaceMetric,
+    pub ascending_node: SpaceMetric,
+    pub argument_of_periapsis: SpaceMetric,
+    pub mean_anomaly_at_epoch: SpaceMetric,
+}
+
+impl OrbitalElements {
+    /// A circular orbit, `offset` being the angle along the orbit at time zero.
+    pub fn circular(
+        semimajor_axis: SpaceMetric,
+        inclination: SpaceMetric,
+        ascending_node: SpaceMetric,
+        offset: SpaceMetric,
+    ) -> Self {
+        Self {
+            semimajor_axis,
+            eccentricity: 0.,
+            inclination,
+            ascending_node,
+            argument_of_periapsis: 0.,
+            mean_anomaly_at_epoch: offset,
+        }
+    }
+
+    /// Orbital period in nanoseconds around a central body of `central_mass`.
+    pub fn period(&self, central_mass: SpaceMetric) -> SpaceMetric {
+        2. * PI * (self.semimajor_axis.powi(3) / (G * central_mass)).sqrt() * 1_000_000_000.
+    }
+
+    /// True anomaly and distance from the central body at a mean anomaly.
+    pub fn true_anomaly_and_radius(&self, mean_anomaly: SpaceMetric) -> (SpaceMetric, SpaceMetric) {
+        let e = self.eccentricity;
+        let eccentric_anomaly = eccentric_anomaly(mean_anomaly, e);
+        let true_anomaly = 2. * ((1. + e).sqrt() * (eccentric_anomaly / 2.).sin())
+            .atan2((1. - e).sqrt() * (eccentric_anomaly / 2.).cos());
+        (true_anomaly, self.semimajor_axis * (1. - e * eccentric_anomaly.cos()))
+    }
+}
+
+/// Solves Kepler's equation `M = E - e sin E` for the eccentric anomaly `E` by Newton's method.
+pub fn eccentric_anomaly(mean_anomaly: SpaceMetric, eccentricity: SpaceMetric) -> SpaceMetric {
+    assert!((0. ..1.).contains(&eccentricity), "only elliptical orbits are supported");
+
+    // Reduce to (-π, π] where the starting guess converges for every eccentricity.
+    let turns = (mean_anomaly / (2. * PI)).round();
+    let mean_anomaly = mean_anomaly - turns * 2. * PI;
+
+    let mut eccentric_anomaly = if eccentricity < 0.8 { mean_anomaly } else { PI.copysign(mean_anomaly) };
+    for _ in 0..KEPLER_MAX_ITERATIONS {
+        let step = (eccentric_anomaly - eccentricity * eccentric_anomaly.sin() - mean_anomaly)
+            / (1. - eccentricity * eccentric_anomaly.cos());
+        eccentric_anomaly -= step;
+        if step.abs() < KEPLER_TOLERANCE {
+            break;
+        }
+    }
+    eccentric_anomaly + turns * 2. * PI
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::movements::orbital_elements::{eccentric_anomaly, OrbitalElements};
+
+    #[test]
+    fn test_kepler_equation() {
+        for &eccentricity in &[0., 0.1, 0.74, 0.97] {
+            for step in -20..20 {
+                let mean_anomaly = step as f64 * 0.5;
+                let e = eccentric_anomaly(mean_anomaly, eccentricity);
+                assert!((e - eccentricity * e.sin() - mean_anomaly).abs() < 1e-9);
+            }
+        }
+
+        let elements = OrbitalElements {
+            semimajor_axis: 26_600_000.,
+            eccentricity: 0.74,
+            inclination: 1.1,
+            ascending_node: 0.,
+            argument_of_periapsis: 4.71,
+            mean_anomaly_at_epoch: 0.,
+        };
+        let (true_anomaly, radius) = elements.true_anomaly_and_radius(std::f64::consts::PI);
+        assert!((true_anomaly.abs() - std::f64::consts::PI).abs() < 1e-9);
+        assert!((radius - 26_600_000. * 1.74).abs() < 1e-3);
+    }
+}
@@ -6,20 +6,20 @@ use cgmath::{EuclideanSpace, Euler, Point3, Quaternion, Rad, Rotation, Vector3};
 
 use crate::body::Body;
 use crate::movement::Movement;
+use crate::movements::orbital_elements::OrbitalElements;
 use crate::utils::{G, SpaceMetric, TimeMetric};
 use crate::utils::Shared;
 
+/// A Keplerian orbit around a moving central body.
 #[derive(Clone)]
 pub struct OrbitalMovement {
     central_body: Shared<Body>,
-    semimajor_axis: SpaceMetric,
-    inclination: SpaceMetric,
+    pub elements: OrbitalElements,
     pub period: SpaceMetric,
-    offset: SpaceMetric,
-    ascending_node_offset: SpaceMetric,
 }
 
 impl OrbitalMovement {
+    /// A circular orbit.
     pub fn new(
         central_body: &Shared<Body>,
         semimajor_axis: SpaceMetric,
@@ -28,38 +28,112 @@ impl OrbitalMovement {
         ascending_node_offset: SpaceMetric,
         clockwise: bool,
     ) -> Self {
+        Self::elliptical(
+            central_body,
+            OrbitalElements::circular(semimajor_axis, inclination, ascending_node_offset, offset),
+            clockwise,
+        )
+    }
+
+    pub fn elliptical(central_body: &Shared<Body>, elements: OrbitalElements, clockwise: bool) -> Self {
         Self {
             central_body: Rc::clone(central_body),
-            semimajor_axis,
-            inclination,
-            period: 2.
-                * PI as SpaceMetric
-                * (semimajor_axis.powi(3) / (G * central_body.borrow().mass)).sqrt()
-                * 1_000_000_000.
-                * if clockwise { -1. } else { 1. },
-            offset,
-            ascending_node_offset,
+            elements,
+            period: elements.period(central_body.borrow().mass) * if clockwise { -1. } else { 1. },
         }
     }
+
+    pub fn mean_anomaly_at(&self, time: TimeMetric) -> SpaceMetric {
+        time as SpaceMetric / self.period * 2. * (PI as SpaceMetric) + self.elements.mean_anomaly_at_epoch
+    }
+
+    /// Rotation from a frame whose x axis points from the central body to the orbiting point.
+    fn orbital_plane_rotation(&self, true_anomaly: SpaceMetric) -> Quaternion<SpaceMetric> {
+        Quaternion::from(Euler::new(
+            Rad(0.),
+            Rad(self.elements.ascending_node),
+            Rad(0.),
+        )) * Quaternion::from(Euler::new(
+            Rad(self.elements.inclination),
+            Rad(self.elements.argument_of_periapsis + true_anomaly),
+            Rad(0.),
+        ))
+    }
 }
 
 impl Movement for OrbitalMovement {
     fn get_position_at(&self, time: TimeMetric) -> Point3<SpaceMetric> {
+        let (true_anomaly, radius) = self.elements.true_anomaly_and_radius(self.mean_anomaly_at(time));
+
         Point3::from_vec(
             self.central_body
                 .borrow()
                 .movement
                 .get_position_at(time)
                 .to_vec() +
-                Quaternion::from(Euler::new(
-                    Rad(0.),
-                    Rad(self.ascending_node_offset),
-                    Rad(0.),
-                )) * Quaternion::from(Euler::new(
-                    Rad(self.inclination),
-                    Rad(time as SpaceMetric / self.period * 2. * (PI as SpaceMetric) + self.offset),
-                    Rad(0.),
-                )).rotate_vector(Vector3::new(self.semimajor_axis, 0., 0.)),
+                self.orbital_plane_rotation(true_anomaly).rotate_vector(Vector3::new(radius, 0., 0.)),
         )
     }
+
+    fn get_velocity_at(&self, time: TimeMetric) -> Vector3<SpaceMetric> {
+        let (true_anomaly, _) = self.elements.true_anomaly_and_radius(self.mean_anomaly_at(time));
+        let e = self.elements.eccentricity;
+        let central_body = self.central_body.borrow();
+        let semilatus_rectum = self.elements.semimajor_axis * (1. - e * e);
+        let speed = (G * central_body.mass / semilatus_rectum).sqrt() * self.period.signum();
+
+        // Radial and transverse components, the transverse direction being along the orbit.
+        central_body.movement.get_velocity_at(time) +
+            self.orbital_plane_rotation(true_anomaly).rotate_vector(Vector3::new(
+                speed * e * true_anomaly.sin(),
+                0.,
+                -speed * (1. + e * true_anomaly.cos()),
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{EuclideanSpace, InnerSpace, Point3};
+
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::movement::Movement;
+    use crate::movements::orbital_elements::OrbitalElements;
+    use crate::movements::orbital_movement::OrbitalMovement;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::utils::G;
+
+    #[test]
+    fn test_orbital_velocity() {
+        let builder = IpnSimBuilder::new(0)
+            .add_body("Earth", 5.972e24, StaticMovement::new(Point3::origin()), 6.371e6);
+        let earth = builder.get_body(0);
+        let moon = OrbitalMovement::new(&earth, 384_400e3, 0.09, 1., 2., false);
+        let elements = OrbitalElements {
+            semimajor_axis: 26_600_000.,
+            eccentricity: 0.74,
+            inclination: 1.1,
+            ascending_node: 0.3,
+            argument_of_periapsis: 4.71,
+            mean_anomaly_at_epoch: 0.,
+        };
+
+        for movement in &[
+            moon.clone(),
+            OrbitalMovement::elliptical(&earth, elements, false),
+            OrbitalMovement::elliptical(&earth, elements, true),
+        ] {
+            for step in 0..20 {
+                let time = (movement.period.abs() * step as f64 / 20.) as u64 + 1_000_000_000;
+                let estimate = (movement.get_position_at(time + 1_000_000) - movement.get_position_at(time - 1_000_000)) / 2e-3;
+                let velocity = movement.get_velocity_at(time);
+                assert!((velocity - estimate).magnitude() < 1e-3 * velocity.magnitude());
+            }
+        }
+
+        // Perigee speed from the vis-viva equation.
+        let perigee_speed = (G * 5.972e24 * (2. / (26_600_000. * 0.26) - 1. / 26_600_000.)).sqrt();
+        let speed = OrbitalMovement::elliptical(&earth, elements, false).get_velocity_at(0).magnitude();
+        assert!((speed / perigee_speed - 1.).abs() < 1e-9);
+    }
 }
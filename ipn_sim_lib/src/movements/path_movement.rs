@@ -1,10 +1,12 @@
-use cgmath::Point3;
+use cgmath::{Point3, Vector3, Zero};
 
 use crate::movement::Movement;
 use crate::utils::{SpaceMetric, TimeMetric};
 
 type PathPosition = (TimeMetric, Point3<SpaceMetric>);
 
+/// Linear interpolation between waypoints sorted by time, holding the first and last waypoints
+/// outside of their range.
 #[derive(Clone)]
 pub struct PathMovement {
     positions: Vec<PathPosition>,
@@ -14,21 +16,71 @@ impl PathMovement {
     pub fn new(positions: Vec<PathPosition>) -> Self {
         PathMovement { positions }
     }
+
+    /// The waypoints either side of `time`, or `None` outside of the path.
+    fn segment(&self, time: TimeMetric) -> Option<(&PathPosition, &PathPosition)> {
+        let index = self.positions.partition_point(|position| position.0 <= time);
+        if index == 0 || index == self.positions.len() {
+            None
+        } else {
+            Some((&self.positions[index - 1], &self.positions[index]))
+        }
+    }
 }
 
 impl Movement for PathMovement {
     fn get_position_at(&self, time: u64) -> Point3<SpaceMetric> {
-        for (index, position) in self.positions.iter().enumerate() {
-            if position.0 == time {
-                return position.1;
-            }
-            if position.0 > time {
-                let prev_position = &self.positions[index - 1];
+        match self.segment(time) {
+            Some((prev_position, position)) => {
                 let inter_time =
                     (time - prev_position.0) as SpaceMetric / (position.0 - prev_position.0) as SpaceMetric;
-                return prev_position.1 + ((position.1 - prev_position.1) * inter_time);
+                prev_position.1 + ((position.1 - prev_position.1) * inter_time)
+            }
+            None if time < self.positions[0].0 => self.positions[0].1,
+            None => self.positions.last().unwrap().1,
+        }
+    }
+
+    fn get_velocity_at(&self, time: TimeMetric) -> Vector3<SpaceMetric> {
+        match self.segment(time) {
+            Some((prev_position, position)) => {
+                (position.1 - prev_position.1) / ((position.0 - prev_position.0) as SpaceMetric / 1e9)
             }
+            None => Vector3::zero(),
         }
-        self.positions.last().unwrap().1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Point3, Vector3};
+
+    use crate::movement::Movement;
+    use crate::movement::tests::test_movement;
+    use crate::movements::path_movement::PathMovement;
+
+    #[test]
+    fn test_path_movement() {
+        test_movement(
+            PathMovement::new(vec![
+                (10, Point3::new(0., 0., 0.)),
+                (20, Point3::new(10., 0., 0.)),
+                (40, Point3::new(10., 20., 0.)),
+            ]),
+            vec![
+                (0, Point3::new(0., 0., 0.)),
+                (15, Point3::new(5., 0., 0.)),
+                (20, Point3::new(10., 0., 0.)),
+                (30, Point3::new(10., 10., 0.)),
+                (50, Point3::new(10., 20., 0.)),
+            ].into_iter(),
+        );
+
+        let movement = PathMovement::new(vec![
+            (0, Point3::new(0., 0., 0.)),
+            (2_000_000_000, Point3::new(10., 0., 0.)),
+        ]);
+        assert_eq!(movement.get_velocity_at(1_000_000_000), Vector3::new(5., 0., 0.));
+        assert_eq!(movement.get_velocity_at(3_000_000_000), Vector3::new(0., 0., 0.));
     }
 }
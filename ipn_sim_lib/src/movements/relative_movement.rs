@@ -0,0 +1,105 @@
+use std::rc::Rc;
+
+use cgmath::{EuclideanSpace, Point3, Vector3};
+
+use crate::movement::Movement;
+use crate::node::node::Node;
+use crate::utils::{Shared, SpaceMetric, TimeMetric};
+
+#[derive(Clone)]
+pub enum Leader {
+    Movement(Box<dyn Movement>),
+    Node(Shared<Node>),
+}
+
+/// Offsets a leader's movement by another movement, whose positions are taken as offsets in the
+/// sim frame, for formation flying around a leader node.
+#[derive(Clone)]
+pub struct RelativeMovement {
+    pub leader: Leader,
+    offset: Box<dyn Movement>,
+}
+
+impl RelativeMovement {
+    pub fn new(leader: impl Movement + 'static, offset: impl Movement + 'static) -> Self {
+        Self {
+            leader: Leader::Movement(Box::new(leader)),
+            offset: Box::new(offset),
+        }
+    }
+
+    /// Follows a node, which must not itself follow this movement.
+    pub fn following(leader: &Shared<Node>, offset: impl Movement + 'static) -> Self {
+        Self {
+            leader: Leader::Node(Rc::clone(leader)),
+            offset: Box::new(offset),
+        }
+    }
+}
+
+impl Movement for RelativeMovement {
+    fn get_position_at(&self, time: TimeMetric) -> Point3<SpaceMetric> {
+        let leader_position = match &self.leader {
+            Leader::Movement(movement) => movement.get_position_at(time),
+            Leader::Node(node) => node.borrow().movement.get_position_at(time),
+        };
+        leader_position + self.offset.get_position_at(time).to_vec()
+    }
+
+    fn get_velocity_at(&self, time: TimeMetric) -> Vector3<SpaceMetric> {
+        let leader_velocity = match &self.leader {
+            Leader::Movement(movement) => movement.get_velocity_at(time),
+            Leader::Node(node) => node.borrow().movement.get_velocity_at(time),
+        };
+        leader_velocity + self.offset.get_velocity_at(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{EuclideanSpace, MetricSpace, Point3, Vector3};
+
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::movements::free_flight_movement::FreeFlightMovement;
+    use crate::movements::orbital_movement::OrbitalMovement;
+    use crate::movements::relative_movement::RelativeMovement;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::node::node_builder::NodeBuilder;
+    use crate::routers::test_router::TestRouter;
+    use crate::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
+
+    #[test]
+    fn test_formation_around_leader_node() {
+        let mut node_builder = NodeBuilder::new();
+        node_builder
+            .name("leader")
+            .movement(FreeFlightMovement::new(Point3::new(1e7, 0., 0.), Vector3::new(0., 0., 7e3)))
+            .message_buffer_size(1)
+            .router(TestRouter)
+            .transceive_speed(1.)
+            .transceive_guard(SimpleTransceiveGuard::new(1e3));
+        let mut builder = IpnSimBuilder::new(0)
+            .add_body("Anchor", 1e3, StaticMovement::new(Point3::origin()), 1.)
+            .add_node(&node_builder);
+        let leader = builder.get_node(0);
+
+        // One follower holds station 100m behind, another circles the leader.
+        let anchor = builder.get_body(0);
+        builder = builder
+            .add_node(node_builder
+                .name("trailing")
+                .movement(RelativeMovement::following(&leader, StaticMovement::new(Point3::new(0., 0., -100.)))))
+            .add_node(node_builder
+                .name("circling")
+                .movement(RelativeMovement::following(&leader, OrbitalMovement::new(&anchor, 50., 0., 0., 0., false))));
+
+        let trailing = builder.get_node(1);
+        let circling = builder.get_node(2);
+        for &time in &[0, 60_000_000_000, 3_600_000_000_000] {
+            let leader_position = leader.borrow().movement.get_position_at(time);
+            assert_eq!(trailing.borrow().movement.get_position_at(time), leader_position + Vector3::new(0., 0., -100.));
+            assert!((circling.borrow().movement.get_position_at(time).distance(leader_position) - 50.).abs() < 1e-6);
+            assert_eq!(trailing.borrow().movement.get_velocity_at(time), Vector3::new(0., 0., 7e3));
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use cgmath::Vector3;
+
+use crate::movements::tle::Tle;
+use crate::utils::SpaceMetric;
+
+// WGS72 constants, as used to fit published element sets.
+const EARTH_RADIUS: SpaceMetric = 6378.135;
+const XKE: SpaceMetric = 0.0743669161331734;
+const J2: SpaceMetric = 0.001082616;
+const J3: SpaceMetric = -0.00000253881;
+const J4: SpaceMetric = -0.00000165597;
+const J3_OVER_J2: SpaceMetric = J3 / J2;
+const TWO_THIRDS: SpaceMetric = 2. / 3.;
+
+/// Periods of this many minutes or more need the deep space SDP4 terms.
+const DEEP_SPACE_PERIOD: SpaceMetric = 225.;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sgp4Error {
+    DeepSpace,
+    Eccentricity,
+    MeanMotion,
+    SemiLatusRectum,
+    Decayed,
+}
+
+impl fmt::Display for Sgp4Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Sgp4Error::DeepSpace => write!(f, "orbits with a period of 225 minutes or more are not supported"),
+            Sgp4Error::Eccentricity => write!(f, "mean eccentricity left [0, 1)"),
+            Sgp4Error::MeanMotion => write!(f, "mean motion became negative"),
+            Sgp4Error::SemiLatusRectum => write!(f, "semi-latus rectum became negative"),
+            Sgp4Error::Decayed => write!(f, "satellite has decayed"),
+        }
+    }
+}
+
+impl std::error::Error for Sgp4Error {}
+
+/// Position and velocity in the TEME frame, in km and km/s.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sgp4State {
+    pub position: Vector3<SpaceMetric>,
+    pub velocity: Vector3<SpaceMetric>,
+}
+
+/// Near Earth SGP4 propagator, following Vallado et al., "Revisiting Spacetrack Report #3" (2006).
+#[derive(Clone, Debug)]
+pub struct Sgp4 {
+    bstar: SpaceMetric,
+    inclination: SpaceMetric,
+    right_ascension: SpaceMetric,
+    eccentricity: SpaceMetric,
+    argument_of_perigee: SpaceMetric,
+    mean_anomaly: SpaceMetric,
+    mean_motion: SpaceMetric,
+    is_simple: bool,
+    eta: SpaceMetric,
+    con41: SpaceMetric,
+    x1mth2: SpaceMetric,
+    x7thm1: SpaceMetric,
+    cc1: SpaceMetric,
+    cc4: SpaceMetric,
+    cc5: SpaceMetric,
+    d2: SpaceMetric,
+    d3: SpaceMetric,
+    d4: SpaceMetric,
+    delmo: SpaceMetric,
+    sinmao: SpaceMetric,
+    mdot: SpaceMetric,
+    argpdot: SpaceMetric,
+    nodedot: SpaceMetric,
+    omgcof: SpaceMetric,
+    xmcof: SpaceMetric,
+    nodecf: SpaceMetric,
+    t2cof: SpaceMetric,
+    t3cof: SpaceMetric,
+    t4cof: SpaceMetric,
+    t5cof: SpaceMetric,
+    xlcof: SpaceMetric,
+    aycof: SpaceMetric,
+}
+
+impl Sgp4 {
+    pub fn new(tle: &Tle) -> Result<Self, Sgp4Error> {
+        let ecco = tle.eccentricity;
+        let inclo = tle.inclination.to_radians();
+        let argpo = tle.argument_of_perigee.to_radians();
+        let mo = tle.mean_anomaly.to_radians();
+        let bstar = tle.bstar;
+        let no_kozai = tle.mean_motion * 2. * PI / 1440.;
+
+        // Recover the Brouwer mean motion and semi-major axis from the Kozai mean motion.
+        let eccsq = ecco * ecco;
+        let omeosq = 1. - eccsq;
+        let rteosq = omeosq.sqrt();
+        let cosio = inclo.cos();
+        let cosio2 = cosio * cosio;
+        let ak = (XKE / no_kozai).powf(TWO_THIRDS);
+        let d1 = 0.75 * J2 * (3. * cosio2 - 1.) / (rteosq * omeosq);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1. - del * del - del * (1. / 3. + 134. * del * del / 81.));
+        let del = d1 / (adel * adel);
+        let no = no_kozai / (1. + del);
+
+        if 2. * PI / no >= DEEP_SPACE_PERIOD {
+            return Err(Sgp4Error::DeepSpace);
+        }
+
+        let ao = (XKE / no).powf(TWO_THIRDS);
+        let sinio = inclo.sin();
+        let po = ao * omeosq;
+        let con42 = 1. - 5. * cosio2;
+        let con41 = -con42 - 2. * cosio2;
+        let posq = po * po;
+        let rp = ao * (1. - ecco);
+
+        // Atmospheric density parameters, lowered for perigees below 156 km.
+        let ss = 78. / EARTH_RADIUS + 1.;
+        let mut sfour = ss;
+        let mut qzms24 = ((120. - 78.) / EARTH_RADIUS).powi(4);
+        let perigee = (rp - 1.) * EARTH_RADIUS;
+        if perigee < 156. {
+            sfour = if perigee < 98. { 20. } else { perigee - 78. };
+            qzms24 = ((120. - sfour) / EARTH_RADIUS).powi(4);
+            sfour = sfour / EARTH_RADIUS + 1.;
+        }
+
+        let pinvsq = 1. / posq;
+        let tsi = 1. / (ao - sfour);
+        let eta = ao * ecco * tsi;
+        let etasq = eta * eta;
+        let eeta = ecco * eta;
+        let psisq = (1. - etasq).abs();
+        let coef = qzms24 * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+        let cc2 = coef1 * no * (ao * (1. + 1.5 * etasq + eeta * (4. + etasq))
+            + 0.375 * J2 * tsi / psisq * con41 * (8. + 3. * etasq * (8. + etasq)));
+        let cc1 = bstar * cc2;
+        let cc3 = if ecco > 1e-4 { -2. * coef * tsi * J3_OVER_J2 * no * sinio / ecco } else { 0. };
+        let x1mth2 = 1. - cosio2;
+        let cc4 = 2. * no * coef1 * ao * omeosq * (eta * (2. + 0.5 * etasq) + ecco * (0.5 + 2. * etasq)
+            - J2 * tsi / (ao * psisq) * (-3. * con41 * (1. - 2. * eeta + etasq * (1.5 - 0.5 * eeta))
+            + 0.75 * x1mth2 * (2. * etasq - eeta * (1. + etasq)) * (2. * argpo).cos()));
+        let cc5 = 2. * coef1 * ao * omeosq * (1. + 2.75 * (etasq + eeta) + eeta * etasq);
+
+        // Secular rates of the mean anomaly, argument of perigee and ascending node.
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2 * pinvsq * no;
+        let temp2 = 0.5 * temp1 * J2 * pinvsq;
+        let temp3 = -0.46875 * J4 * pinvsq * pinvsq * no;
+        let mdot = no + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13. - 78. * cosio2 + 137. * cosio4);
+        let argpdot = -0.5 * temp1 * con42 + 0.0625 * temp2 * (7. - 114. * cosio2 + 395. * cosio4)
+            + temp3 * (3. - 36. * cosio2 + 49. * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        let nodedot = xhdot1 + (0.5 * temp2 * (4. - 19. * cosio2) + 2. * temp3 * (3. - 7. * cosio2)) * cosio;
+
+        let is_simple = rp < 220. / EARTH_RADIUS + 1.;
+        let (mut d2, mut d3, mut d4, mut t3cof, mut t4cof, mut t5cof) = (0., 0., 0., 0., 0., 0.);
+        if !is_simple {
+            let cc1sq = cc1 * cc1;
+            d2 = 4. * ao * tsi * cc1sq;
+            let temp = d2 * tsi * cc1 / 3.;
+            d3 = (17. * ao + sfour) * temp;
+            d4 = 0.5 * temp * ao * tsi * (221. * ao + 31. * sfour) * cc1;
+            t3cof = d2 + 2. * cc1sq;
+            t4cof = 0.25 * (3. * d3 + cc1 * (12. * d2 + 10. * cc1sq));
+            t5cof = 0.2 * (3. * d4 + 12. * cc1 * d3 + 6. * d2 * d2 + 15. * cc1sq * (2. * d2 + cc1sq));
+        }
+
+        Ok(Self {
+            bstar,
+            inclination: inclo,
+            right_ascension: tle.right_ascension.to_radians(),
+            eccentricity: ecco,
+            argument_of_perigee: argpo,
+            mean_anomaly: mo,
+            mean_motion: no,
+            is_simple,
+            eta,
+            con41,
+            x1mth2,
+            x7thm1: 7. * cosio2 - 1.,
+            cc1,
+            cc4,
+            cc5,
+            d2,
+            d3,
+            d4,
+            delmo: (1. + eta * mo.cos()).powi(3),
+            sinmao: mo.sin(),
+            mdot,
+            argpdot,
+            nodedot,
+            omgcof: bstar * cc3 * argpo.cos(),
+            xmcof: if ecco > 1e-4 { -TWO_THIRDS * coef * bstar / eeta } else { 0. },
+            nodecf: 3.5 * omeosq * xhdot1 * cc1,
+            t2cof: 1.5 * cc1,
+            t3cof,
+            t4cof,
+            t5cof,
+            xlcof: -0.25 * J3_OVER_J2 * sinio * (3. + 5. * cosio) / (1. + cosio).max(1.5e-12),
+            aycof: -0.5 * J3_OVER_J2 * sinio,
+        })
+    }
+
+    /// Period of the mean orbit in minutes.
+    pub fn period(&self) -> SpaceMetric {
+        2. * PI / self.mean_motion
+    }
+
+    /// State `minutes` after the element set epoch.
+    pub fn propagate(&self, minutes: SpaceMetric) -> Result<Sgp4State, Sgp4Error> {
+        let t = minutes;
+        let t2 = t * t;
+
+        // Secular gravity and atmospheric drag.
+        let xmdf = self.mean_anomaly + self.mdot * t;
+        let argpdf = self.argument_of_perigee + self.argpdot * t;
+        let nodedf = self.right_ascension + self.nodedot * t;
+        let mut argpm = argpdf;
+        let mut mm = xmdf;
+        let nodem = nodedf + self.nodecf * t2;
+        let mut tempa = 1. - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+
+        if !self.is_simple {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * ((1. + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp = delomg + delm;
+            mm = xmdf + temp;
+            argpm = argpdf - temp;
+            let t3 = t2 * t;
+            let t4 = t3 * t;
+            tempa = tempa - self.d2 * t2 - self.d3 * t3 - self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mm.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        let am = (XKE / self.mean_motion).powf(TWO_THIRDS) * tempa * tempa;
+        let nm = XKE / am.powf(1.5);
+        let mut em = self.eccentricity - tempe;
+        if nm <= 0. {
+            return Err(Sgp4Error::MeanMotion);
+        }
+        if !(-0.001..1.).contains(&em) {
+            return Err(Sgp4Error::Eccentricity);
+        }
+        em = em.max(1e-6);
+
+        mm += self.mean_motion * templ;
+        let xlm = (mm + argpm + nodem) % (2. * PI);
+        let nodem = nodem % (2. * PI);
+        let argpm = argpm % (2. * PI);
+        let mm = (xlm - argpm - nodem) % (2. * PI);
+
+        // Long period periodics.
+        let axnl = em * argpm.cos();
+        let temp = 1. / (am * (1. - em * em));
+        let aynl = em * argpm.sin() + temp * self.aycof;
+        let xl = mm + argpm + nodem + temp * self.xlcof * axnl;
+
+        // Kepler's equation in the equinoctial form.
+        let u = (xl - nodem) % (2. * PI);
+        let mut eo1 = u;
+        let (mut sineo1, mut coseo1) = (0., 0.);
+        for _ in 0..10 {
+            sineo1 = eo1.sin();
+            coseo1 = eo1.cos();
+            let step = (u - aynl * coseo1 + axnl * sineo1 - eo1) / (1. - coseo1 * axnl - sineo1 * aynl);
+            eo1 += step.clamp(-0.95, 0.95);
+            if step.abs() < 1e-12 {
+                break;
+            }
+        }
+
+        // Short period periodics.
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1. - el2);
+        if pl < 0. {
+            return Err(Sgp4Error::SemiLatusRectum);
+        }
+
+        let rl = am * (1. - ecose);
+        let rdotl = am.sqrt() * esine / rl;
+        let rvdotl = pl.sqrt() / rl;
+        let betal = (1. - el2).sqrt();
+        let temp = esine / (1. + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let su = sinu.atan2(cosu);
+        let sin2u = 2. * cosu * sinu;
+        let cos2u = 1. - 2. * sinu * sinu;
+        let temp1 = 0.5 * J2 / pl;
+        let temp2 = temp1 / pl;
+
+        let mrt = rl * (1. - 1.5 * temp2 * betal * self.con41) + 0.5 * temp1 * self.x1mth2 * cos2u;
+        let su = su - 0.25 * temp2 * self.x7thm1 * sin2u;
+        let xnode = nodem + 1.5 * temp2 * self.inclination.cos() * sin2u;
+        let xinc = self.inclination + 1.5 * temp2 * self.inclination.cos() * self.inclination.sin() * cos2u;
+        let mvt = rdotl - nm * temp1 * self.x1mth2 * sin2u / XKE;
+        let rvdot = rvdotl + nm * temp1 * (self.x1mth2 * cos2u + 1.5 * self.con41) / XKE;
+
+        if mrt < 1. {
+            return Err(Sgp4Error::Decayed);
+        }
+
+        // Orientation vectors.
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx = -snod * cosi;
+        let xmy = cnod * cosi;
+        let u = Vector3::new(xmx * sinsu + cnod * cossu, xmy * sinsu + snod * cossu, sini * sinsu);
+        let v = Vector3::new(xmx * cossu - cnod * sinsu, xmy * cossu - snod * sinsu, sini * cossu);
+
+        Ok(Sgp4State {
+            position: u * mrt * EARTH_RADIUS,
+            velocity: (u * mvt + v * rvdot) * EARTH_RADIUS * XKE / 60.,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::movements::sgp4::{Sgp4, Sgp4Error};
+    use crate::movements::tle::Tle;
+
+    #[test]
+    fn test_sgp4_verification_case() {
+        let tle = Tle::parse(
+            None,
+            "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+            "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+        ).unwrap();
+        assert_eq!(tle.catalogue_number, 5);
+        assert!((tle.bstar - 0.28098e-4).abs() < 1e-12);
+
+        let sgp4 = Sgp4::new(&tle).unwrap();
+        for &(minutes, expected) in &[
+            (0., [7022.46529266, -1400.08296755, 0.03995155]),
+            (360., [-7154.03120202, -3783.17682504, -3536.19412294]),
+        ] {
+            let position = sgp4.propagate(minutes).unwrap().position;
+            assert!((position.x - expected[0]).abs() < 1e-3);
+            assert!((position.y - expected[1]).abs() < 1e-3);
+            assert!((position.z - expected[2]).abs() < 1e-3);
+        }
+
+        let geostationary = Tle::parse(
+            None,
+            "1 28626U 05008A   06176.46683397 -.00000205  00000-0  10000-3 0  2190",
+            "2 28626   0.0019 286.9433 0000335  13.7918  55.6504  1.00270176  4891",
+        ).unwrap();
+        assert_eq!(Sgp4::new(&geostationary).err(), Some(Sgp4Error::DeepSpace));
+    }
+}
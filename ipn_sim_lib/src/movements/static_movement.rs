@@ -1,6 +1,6 @@
 use crate::movement::Movement;
 use crate::utils::{SpaceMetric, TimeMetric};
-use cgmath::Point3;
+use cgmath::{Point3, Vector3, Zero};
 
 #[derive(Clone)]
 pub struct StaticMovement {
@@ -17,4 +17,8 @@ impl Movement for StaticMovement {
     fn get_position_at(&self, _time: TimeMetric) -> Point3<SpaceMetric> {
         self.point
     }
+
+    fn get_velocity_at(&self, _time: TimeMetric) -> Vector3<SpaceMetric> {
+        Vector3::zero()
+    }
 }
@@ -0,0 +1,132 @@
+use std::rc::Rc;
+
+use cgmath::{Point3, Rotation, Vector3};
+
+use crate::body::Body;
+use crate::movement::Movement;
+use crate::node::node::Node;
+use crate::utils::{Shared, SpaceMetric, TimeMetric};
+
+/// A point fixed to the surface of a rotating body, such as a ground station or lander.
+/// Latitude and longitude are in radians, longitude increasing east from the prime meridian, and
+/// altitude is in metres above the surface. On an oblate body the latitude is geocentric.
+#[derive(Clone)]
+pub struct SurfaceMovement {
+    body: Shared<Body>,
+    pub latitude: SpaceMetric,
+    pub longitude: SpaceMetric,
+    pub altitude: SpaceMetric,
+}
+
+impl SurfaceMovement {
+    pub fn new(body: &Shared<Body>, latitude: SpaceMetric, longitude: SpaceMetric, altitude: SpaceMetric) -> Self {
+        Self {
+            body: Rc::clone(body),
+            latitude,
+            longitude,
+            altitude,
+        }
+    }
+
+    pub fn body(&self) -> &Shared<Body> {
+        &self.body
+    }
+
+    /// Whether the node stands on the surface of `body`.
+    pub fn is_on(node: &Node, body: &Shared<Body>) -> bool {
+        node.movement
+            .downcast_ref::<SurfaceMovement>()
+            .is_some_and(|surface_movement| Rc::ptr_eq(&surface_movement.body, body))
+    }
+
+    /// Outward unit normal of the surface in the body fixed frame.
+    pub fn local_up(&self) -> Vector3<SpaceMetric> {
+        Vector3::new(
+            self.latitude.cos() * self.longitude.cos(),
+            self.latitude.sin(),
+            -self.latitude.cos() * self.longitude.sin(),
+        )
+    }
+
+    /// Outward unit normal of the surface in the sim frame.
+    pub fn up_at(&self, time: TimeMetric) -> Vector3<SpaceMetric> {
+        self.body.borrow().rotation.orientation_at(time).rotate_vector(self.local_up())
+    }
+}
+
+impl Movement for SurfaceMovement {
+    fn get_position_at(&self, time: TimeMetric) -> Point3<SpaceMetric> {
+        let body = self.body.borrow();
+        body.movement.get_position_at(time)
+            + body.rotation.orientation_at(time).rotate_vector(self.local_up()) * (body.radius_at(self.latitude) + self.altitude)
+    }
+
+    fn get_velocity_at(&self, time: TimeMetric) -> Vector3<SpaceMetric> {
+        let body = self.body.borrow();
+        let offset = body.rotation.orientation_at(time).rotate_vector(self.local_up()) * (body.radius_at(self.latitude) + self.altitude);
+        body.movement.get_velocity_at(time) + body.rotation.angular_velocity().cross(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use cgmath::{EuclideanSpace, InnerSpace, Point3};
+
+    use crate::body::BodyRotation;
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::movement::Movement;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::movements::surface_movement::SurfaceMovement;
+    use crate::node::node_builder::NodeBuilder;
+    use crate::routers::test_router::TestRouter;
+    use crate::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
+
+    #[test]
+    fn test_surface_rotates_with_body() {
+        let day = 86_400_000_000_000;
+        let builder = IpnSimBuilder::new(day).add_rotating_body(
+            "Planet",
+            1e24,
+            StaticMovement::new(Point3::new(1e9, 0., 0.)),
+            1e6,
+            BodyRotation::new(day as f64, 0., 0.),
+        );
+        let station = SurfaceMovement::new(&builder.get_body(0), 0., 0., 1e3);
+
+        let offset = |time| station.get_position_at(time) - Point3::new(1e9, 0., 0.);
+        assert!((offset(0) - cgmath::Vector3::new(1.001e6, 0., 0.)).magnitude() < 1e-6);
+        // A quarter of a day later, prograde rotation has carried the station from +x to -z.
+        assert!((offset(day / 4) - cgmath::Vector3::new(0., 0., -1.001e6)).magnitude() < 1e-3);
+        assert!((offset(day) - offset(0)).magnitude() < 1e-3);
+        let speed = 2. * PI * 1.001e6 / 86_400.;
+        assert!((station.get_velocity_at(0) - cgmath::Vector3::new(0., 0., -speed)).magnitude() < 1e-9);
+
+        let pole = SurfaceMovement::new(&builder.get_body(0), PI / 2., 0., 0.);
+        assert!((pole.get_position_at(day / 3).to_vec() - cgmath::Vector3::new(1e9, 1e6, 0.)).magnitude() < 1e-3);
+
+        // The station sees a node beyond its horizon only while the planet turns it to face it.
+        let sim = builder
+            .add_node(NodeBuilder::new()
+                .name("station")
+                .movement(station)
+                .message_buffer_size(1)
+                .router(TestRouter)
+                .transceive_speed(1.)
+                .transceive_guard(SimpleTransceiveGuard::new(1e12)))
+            .add_node(NodeBuilder::new()
+                .name("relay")
+                .movement(StaticMovement::new(Point3::new(2e9, 0., 0.)))
+                .message_buffer_size(1)
+                .router(TestRouter)
+                .transceive_speed(1.)
+                .transceive_guard(SimpleTransceiveGuard::new(1e12)))
+            .build();
+        for &(time, covisible) in &[(0, true), (day / 2, false), (day, true)] {
+            sim.bodies[0].borrow_mut().set_position(time);
+            sim.get_node(0).borrow_mut().set_position(time);
+            assert_eq!(sim.nodes_are_covisible(&sim.get_node(0).borrow(), &sim.get_node(1).borrow(), &[]), covisible);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use crate::ipn_sim::epoch::Epoch;
+use crate::utils::SpaceMetric;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TleError {
+    LineLength { line: u8 },
+    LineNumber { line: u8 },
+    Checksum { line: u8 },
+    CatalogueMismatch,
+    Field { line: u8, name: &'static str },
+    MissingLine,
+}
+
+impl fmt::Display for TleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TleError::LineLength { line } => write!(f, "line {} is shorter than 69 characters", line),
+            TleError::LineNumber { line } => write!(f, "line {} does not start with {}", line, line),
+            TleError::Checksum { line } => write!(f, "checksum of line {} does not match", line),
+            TleError::CatalogueMismatch => write!(f, "lines 1 and 2 have different catalogue numbers"),
+            TleError::Field { line, name } => write!(f, "invalid {} on line {}", name, line),
+            TleError::MissingLine => write!(f, "element set is missing a line"),
+        }
+    }
+}
+
+impl std::error::Error for TleError {}
+
+/// A NORAD two line element set. Angles are in degrees and the mean motion in revolutions per
+/// day, as written.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tle {
+    pub name: Option<String>,
+    pub catalogue_number: u32,
+    pub epoch: Epoch,
+    pub bstar: SpaceMetric,
+    pub inclination: SpaceMetric,
+    pub right_ascension: SpaceMetric,
+    pub eccentricity: SpaceMetric,
+    pub argument_of_perigee: SpaceMetric,
+    pub mean_anomaly: SpaceMetric,
+    pub mean_motion: SpaceMetric,
+}
+
+impl Tle {
+    pub fn parse(name: Option<&str>, line_1: &str, line_2: &str) -> Result<Self, TleError> {
+        let line_1 = check_line(line_1, 1)?;
+        let line_2 = check_line(line_2, 2)?;
+
+        let catalogue_number = field(line_1, 1, 2..7, "catalogue number")?;
+        if field::<u32>(line_2, 2, 2..7, "catalogue number")? != catalogue_number {
+            return Err(TleError::CatalogueMismatch);
+        }
+
+        let epoch_year: i32 = field(line_1, 1, 18..20, "epoch year")?;
+        let epoch_day = field(line_1, 1, 20..32, "epoch day")?;
+
+        Ok(Self {
+            name: name.map(|name| name.trim().to_string()),
+            catalogue_number,
+            epoch: Epoch::from_day_of_year(
+                if epoch_year < 57 { 2000 + epoch_year } else { 1900 + epoch_year },
+                epoch_day,
+            ),
+            bstar: implied_decimal(&line_1[53..61]).ok_or(TleError::Field { line: 1, name: "bstar" })?,
+            inclination: field(line_2, 2, 8..16, "inclination")?,
+            right_ascension: field(line_2, 2, 17..25, "right ascension")?,
+            eccentricity: format!("0.{}", line_2[26..33].trim())
+                .parse()
+                .map_err(|_| TleError::Field { line: 2, name: "eccentricity" })?,
+            argument_of_perigee: field(line_2, 2, 34..42, "argument of perigee")?,
+            mean_anomaly: field(line_2, 2, 43..51, "mean anomaly")?,
+            mean_motion: field(line_2, 2, 52..63, "mean motion")?,
+        })
+    }
+
+    /// Parses every element set in a file, with or without a name line before each.
+    pub fn parse_all(text: &str) -> Result<Vec<Self>, TleError> {
+        let mut tles = vec![];
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        while let Some(line) = lines.next() {
+            let (name, line_1) = if line.starts_with("1 ") {
+                (None, line)
+            } else {
+                (Some(line), lines.next().ok_or(TleError::MissingLine)?)
+            };
+            let line_2 = lines.next().ok_or(TleError::MissingLine)?;
+            tles.push(Self::parse(name, line_1, line_2)?);
+        }
+        Ok(tles)
+    }
+}
+
+fn check_line(line: &str, number: u8) -> Result<&str, TleError> {
+    let line = line.trim_end();
+    if line.len() < 69 || !line.is_ascii() {
+        return Err(TleError::LineLength { line: number });
+    }
+    if !line.starts_with(&format!("{} ", number)) {
+        return Err(TleError::LineNumber { line: number });
+    }
+
+    let checksum = line[..68]
+        .chars()
+        .map(|c| match c {
+            '-' => 1,
+            _ => c.to_digit(10).unwrap_or(0),
+        })
+        .sum::<u32>() % 10;
+    if line[68..69].parse() != Ok(checksum) {
+        return Err(TleError::Checksum { line: number });
+    }
+    Ok(line)
+}
+
+fn field<T: std::str::FromStr>(line: &str, number: u8, range: std::ops::Range<usize>, name: &'static str) -> Result<T, TleError> {
+    line[range].trim().parse().map_err(|_| TleError::Field { line: number, name })
+}
+
+/// Parses fields such as ` 28098-4`, meaning 0.28098e-4.
+fn implied_decimal(text: &str) -> Option<SpaceMetric> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Some(0.);
+    }
+    let (sign, text) = match text.strip_prefix('-') {
+        Some(text) => (-1., text),
+        None => (1., text.strip_prefix('+').unwrap_or(text)),
+    };
+    let split = text.rfind(['-', '+'])?;
+    let mantissa: SpaceMetric = format!("0.{}", &text[..split]).parse().ok()?;
+    let exponent: i32 = text[split..].parse().ok()?;
+    Some(sign * mantissa * 10f64.powi(exponent))
+}
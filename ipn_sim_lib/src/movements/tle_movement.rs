@@ -0,0 +1,70 @@
+use std::rc::Rc;
+
+use cgmath::{EuclideanSpace, Point3, Vector3};
+
+use crate::body::Body;
+use crate::ipn_sim::epoch::Epoch;
+use crate::movement::Movement;
+use crate::movements::sgp4::{Sgp4, Sgp4Error};
+use crate::movements::tle::Tle;
+use crate::utils::{Shared, SpaceMetric, TimeMetric};
+
+/// An Earth satellite propagated with SGP4 from a two line element set. `sim_epoch` is the
+/// calendar instant of sim time zero, normally the scenario's epoch.
+#[derive(Clone)]
+pub struct TleMovement {
+    central_body: Shared<Body>,
+    pub tle: Tle,
+    pub sim_epoch: Epoch,
+    sgp4: Sgp4,
+}
+
+impl TleMovement {
+    pub fn new(central_body: &Shared<Body>, tle: Tle, sim_epoch: Epoch) -> Result<Self, Sgp4Error> {
+        Ok(Self {
+            central_body: Rc::clone(central_body),
+            sgp4: Sgp4::new(&tle)?,
+            tle,
+            sim_epoch,
+        })
+    }
+
+    /// Orbital period in nanoseconds.
+    pub fn period(&self) -> SpaceMetric {
+        self.sgp4.period() * 60_000_000_000.
+    }
+
+    /// Maps the TEME frame, z towards the pole, onto the sim frame where y is the polar axis.
+    fn to_sim_frame(teme: Vector3<SpaceMetric>) -> Vector3<SpaceMetric> {
+        Vector3::new(teme.x, teme.z, -teme.y) * 1000.
+    }
+}
+
+impl Movement for TleMovement {
+    fn get_position_at(&self, time: TimeMetric) -> Point3<SpaceMetric> {
+        let central_position = self.central_body
+            .borrow()
+            .movement
+            .get_position_at(time);
+        let minutes = self.sim_epoch.at_sim_time(time).minutes_since(&self.tle.epoch);
+
+        // A decayed satellite is left at the centre of the body, where it is occluded.
+        match self.sgp4.propagate(minutes) {
+            Ok(state) => Point3::from_vec(central_position.to_vec() + Self::to_sim_frame(state.position)),
+            Err(_) => central_position,
+        }
+    }
+
+    fn get_velocity_at(&self, time: TimeMetric) -> Vector3<SpaceMetric> {
+        let central_velocity = self.central_body
+            .borrow()
+            .movement
+            .get_velocity_at(time);
+        let minutes = self.sim_epoch.at_sim_time(time).minutes_since(&self.tle.epoch);
+
+        match self.sgp4.propagate(minutes) {
+            Ok(state) => central_velocity + Self::to_sim_frame(state.velocity),
+            Err(_) => central_velocity,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use rustc_hash::FxHashMap;
+
+use crate::bundle::bundle::BundleId;
+use crate::node::message_buffer::MessageHandle;
+use crate::utils::{NodeId, TimeMetric};
+
+/// A bundle handed to a neighbour whose custody signal has not yet been received.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingCustody {
+    pub bundle_id: BundleId,
+    pub handle: MessageHandle,
+    pub to_id: NodeId,
+    pub sent_at: TimeMetric,
+    pub attempts: u8,
+}
+
+/// The bundles a node is custodian of while it awaits custody signals. Transfers not signalled
+/// within the retransmission timeout are handed back to the router to be sent again.
+pub struct CustodyTransfers {
+    pub retransmission_timeout: TimeMetric,
+    pending: FxHashMap<BundleId, PendingCustody>,
+}
+
+impl CustodyTransfers {
+    pub fn new(retransmission_timeout: TimeMetric) -> Self {
+        Self {
+            retransmission_timeout,
+            pending: FxHashMap::default(),
+        }
+    }
+
+    /// Starts the retransmission timer of a bundle. Requesting custody of a bundle already pending
+    /// counts as another attempt.
+    pub fn request(&mut self, bundle_id: BundleId, handle: MessageHandle, to_id: NodeId, time: TimeMetric) {
+        let attempts = self.pending
+            .get(&bundle_id)
+            .map_or(1, |pending_custody| pending_custody.attempts + 1);
+        self.pending.insert(bundle_id, PendingCustody {
+            bundle_id,
+            handle,
+            to_id,
+            sent_at: time,
+            attempts,
+        });
+    }
+
+    pub fn get(&self, bundle_id: &BundleId) -> Option<&PendingCustody> {
+        self.pending.get(bundle_id)
+    }
+
+    pub fn resolve(&mut self, bundle_id: &BundleId) -> Option<PendingCustody> {
+        self.pending.remove(bundle_id)
+    }
+
+    /// Transfers whose timer has run out. They stay pending so their attempts keep counting.
+    pub fn due(&self, time: TimeMetric) -> Vec<PendingCustody> {
+        self.pending
+            .values()
+            .filter(|pending_custody| pending_custody.sent_at + self.retransmission_timeout <= time)
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bundle::bundle::Bundle;
+    use crate::message_destination::MessageDestination;
+    use crate::node::custody_transfers::CustodyTransfers;
+
+    #[test]
+    fn test_custody_transfers() {
+        let bundle_id = Bundle::new(0, 0, MessageDestination::Single(1), vec![].into_boxed_slice(), 0, 100).id();
+        let mut custody_transfers = CustodyTransfers::new(10);
+
+        custody_transfers.request(bundle_id, 0, 1, 0);
+        assert!(custody_transfers.due(9).is_empty());
+        assert_eq!(custody_transfers.due(10)[0].attempts, 1);
+
+        custody_transfers.request(bundle_id, 0, 1, 10);
+        assert_eq!(custody_transfers.due(20)[0].attempts, 2);
+
+        assert!(custody_transfers.resolve(&bundle_id).is_some());
+        assert!(custody_transfers.is_empty());
+    }
+}
@@ -7,6 +7,7 @@ pub type MessageHandle = usize;
 
 pub struct MessageBuffer {
     pub buffer: FxHashMap<MessageHandle, Data>,
+    sizes: FxHashMap<MessageHandle, usize>,
     next_handle: MessageHandle,
     pub size: usize,
     pub capacity: usize,
@@ -16,6 +17,7 @@ impl MessageBuffer {
     pub fn new(capacity: usize) -> Self {
         Self {
             buffer: FxHashMap::default(),
+            sizes: FxHashMap::default(),
             next_handle: 0,
             size: 0,
             capacity,
@@ -23,13 +25,20 @@ impl MessageBuffer {
     }
 
     pub fn add_message(&mut self, data: Data) -> Option<MessageHandle> {
-        if self.size + data.len() > self.capacity {
+        let size = data.len();
+        self.add_sized_message(data, size)
+    }
+
+    /// Stores a message occupying `size` bytes of the buffer, regardless of its in-memory length.
+    pub fn add_sized_message(&mut self, data: Data, size: usize) -> Option<MessageHandle> {
+        if self.size + size > self.capacity {
             return None;
         }
         let handle = self.next_handle;
         self.next_handle += 1;
-        self.size += data.len();
+        self.size += size;
         self.buffer.insert(handle, data);
+        self.sizes.insert(handle, size);
         Some(handle)
     }
 
@@ -39,7 +48,7 @@ impl MessageBuffer {
 
     pub fn remove_message(&mut self, handle: &MessageHandle) -> Option<Data> {
         self.buffer.remove(handle).map(|message| {
-            self.size -= message.len();
+            self.size -= self.sizes.remove(handle).unwrap();
             message
         })
     }
@@ -1,4 +1,6 @@
 pub mod node;
 pub mod node_builder;
 pub mod message_buffer;
+pub mod neighbour_table;
+pub mod custody_transfers;
 
@@ -0,0 +1,67 @@
+use rustc_hash::FxHashMap;
+
+use crate::utils::{NodeId, SpaceMetric, TimeMetric};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Neighbour {
+    pub node_id: NodeId,
+    pub distance: SpaceMetric,
+    pub light_time: TimeMetric,
+}
+
+/// The neighbours a node has discovered. A node in range is only reported once it has been observed
+/// in range for at least the discovery latency, so routers are not omniscient about new contacts.
+/// Nodes are only observed when the router queries its neighbours, so the latency runs from the
+/// first query finding a node in range rather than from the start of the contact. Routers
+/// wanting a latency close to the configured one should query at least that often.
+pub struct NeighbourTable {
+    pub discovery_latency: TimeMetric,
+    first_seen: FxHashMap<NodeId, TimeMetric>,
+}
+
+impl NeighbourTable {
+    pub fn new(discovery_latency: TimeMetric) -> Self {
+        Self {
+            discovery_latency,
+            first_seen: FxHashMap::default(),
+        }
+    }
+
+    /// Records the nodes currently in range and returns those that have been discovered.
+    pub fn update(&mut self, in_range: Vec<Neighbour>, time: TimeMetric) -> Vec<Neighbour> {
+        self.first_seen
+            .retain(|node_id, _| in_range.iter().any(|neighbour| neighbour.node_id == *node_id));
+
+        let discovery_latency = self.discovery_latency;
+        let first_seen = &mut self.first_seen;
+        in_range
+            .into_iter()
+            .filter(|neighbour| {
+                let first_seen = *first_seen.entry(neighbour.node_id).or_insert(time);
+                time - first_seen >= discovery_latency
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::neighbour_table::{Neighbour, NeighbourTable};
+
+    fn neighbour(node_id: u16) -> Neighbour {
+        Neighbour {
+            node_id,
+            distance: 0.,
+            light_time: 0,
+        }
+    }
+
+    #[test]
+    fn test_discovery_latency() {
+        let mut table = NeighbourTable::new(10);
+        assert_eq!(table.update(vec![neighbour(1)], 0), vec![]);
+        assert_eq!(table.update(vec![neighbour(1), neighbour(2)], 10), vec![neighbour(1)]);
+        assert_eq!(table.update(vec![neighbour(2)], 15), vec![]);
+        assert_eq!(table.update(vec![neighbour(1), neighbour(2)], 20), vec![neighbour(2)]);
+    }
+}
@@ -1,9 +1,12 @@
-use cgmath::Point3;
+use cgmath::{Point3, Vector3};
 use serde::Serialize;
 use typescript_definitions::TypescriptDefinition;
 use wasm_bindgen::prelude::*;
 
 use crate::node::message_buffer::MessageBuffer;
+use crate::ltp::engine::LtpEngine;
+use crate::node::custody_transfers::CustodyTransfers;
+use crate::node::neighbour_table::NeighbourTable;
 use crate::movement::Movement;
 use crate::router::Router;
 use crate::transceiver::transceiver::Transceiver;
@@ -16,16 +19,27 @@ pub struct Node {
     #[serde(skip)]
     pub movement: Box<dyn Movement>,
     pub position: Point3<SpaceMetric>,
+    /// Metres per second.
+    pub velocity: Vector3<SpaceMetric>,
     #[serde(skip)]
     pub message_buffer: MessageBuffer,
     #[serde(skip)]
     pub router: Option<Box<dyn Router>>,
     #[serde(skip)]
     pub transceiver: Transceiver,
+    #[serde(skip)]
+    pub neighbour_table: NeighbourTable,
+    #[serde(skip)]
+    pub custody_transfers: CustodyTransfers,
+    #[serde(skip)]
+    pub ltp_engine: Option<LtpEngine>,
+    #[serde(skip)]
+    pub oracle_access: bool,
 }
 
 impl Node {
     pub fn set_position(&mut self, time: TimeMetric) {
-        self.position = self.movement.get_position_at(time)
+        self.position = self.movement.get_position_at(time);
+        self.velocity = self.movement.get_velocity_at(time);
     }
 }
@@ -2,9 +2,12 @@ use crate::movement::Movement;
 use crate::transceiver::transceiver::Transceiver;
 use crate::router::Router;
 use crate::transceiver::transceive_guard::TransceiveGuard;
-use crate::utils::NodeId;
+use crate::utils::{NodeId, TimeMetric};
 use crate::node::node::Node;
 use crate::node::message_buffer::MessageBuffer;
+use crate::ltp::engine::{LtpConfig, LtpEngine};
+use crate::node::custody_transfers::CustodyTransfers;
+use crate::node::neighbour_table::NeighbourTable;
 
 pub struct NodeBuilder {
     name: Option<String>,
@@ -12,7 +15,14 @@ pub struct NodeBuilder {
     message_buffer_size: Option<usize>,
     router: Option<Box<dyn Router>>,
     transceive_speed: Option<f64>,
-    transceive_guard: Option<Box<dyn TransceiveGuard>>
+    transceive_guard: Option<Box<dyn TransceiveGuard>>,
+    frame_header_size: usize,
+    max_frame_size: usize,
+    discovery_latency: TimeMetric,
+    loss_probability: f64,
+    custody_retransmission_timeout: TimeMetric,
+    ltp: Option<LtpConfig>,
+    oracle_access: bool,
 }
 
 impl NodeBuilder {
@@ -23,7 +33,14 @@ impl NodeBuilder {
             message_buffer_size: None,
             router: None,
             transceive_speed: None,
-            transceive_guard: None
+            transceive_guard: None,
+            frame_header_size: 0,
+            max_frame_size: 1500,
+            discovery_latency: 0,
+            loss_probability: 0.,
+            custody_retransmission_timeout: 1_000_000_000 * 60 * 10,
+            ltp: None,
+            oracle_access: false,
         }
     }
 
@@ -65,19 +82,66 @@ impl NodeBuilder {
         self
     }
 
+    pub fn frame_header_size(&mut self, frame_header_size: usize) -> &mut Self {
+        self.frame_header_size = frame_header_size;
+        self
+    }
+
+    pub fn max_frame_size(&mut self, max_frame_size: usize) -> &mut Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    pub fn discovery_latency(&mut self, discovery_latency: TimeMetric) -> &mut Self {
+        self.discovery_latency = discovery_latency;
+        self
+    }
+
+    /// Probability that a packet transmitted by the node is lost before reaching a receiver.
+    pub fn loss_probability(&mut self, loss_probability: f64) -> &mut Self {
+        self.loss_probability = loss_probability;
+        self
+    }
+
+    pub fn custody_retransmission_timeout(&mut self, custody_retransmission_timeout: TimeMetric) -> &mut Self {
+        self.custody_retransmission_timeout = custody_retransmission_timeout;
+        self
+    }
+
+    /// Sends everything the router transmits through an LTP convergence layer.
+    pub fn ltp(&mut self, ltp_config: LtpConfig) -> &mut Self {
+        self.ltp = Some(ltp_config);
+        self
+    }
+
+    /// Grants the node's router omniscient access to the simulation, for benchmark routers only.
+    pub fn oracle_access(&mut self, oracle_access: bool) -> &mut Self {
+        self.oracle_access = oracle_access;
+        self
+    }
+
     pub fn build(&self, node_id: NodeId) -> Node {
         let movement = self.movement.as_ref().unwrap().clone();
+        let mut transceiver = Transceiver::new(
+            self.transceive_speed.unwrap(),
+            self.transceive_guard.as_ref().unwrap().clone()
+        );
+        transceiver.frame_header_size = self.frame_header_size;
+        transceiver.max_frame_size = self.max_frame_size;
+        transceiver.loss_probability = self.loss_probability;
         Node {
             id: node_id,
             name: self.name.as_ref().unwrap().clone(),
             position: movement.get_position_at(0),
+            velocity: movement.get_velocity_at(0),
             movement,
             message_buffer: MessageBuffer::new(self.message_buffer_size.unwrap()),
             router: Some(self.router.as_ref().unwrap().clone()),
-            transceiver: Transceiver::new(
-                self.transceive_speed.unwrap(),
-                self.transceive_guard.as_ref().unwrap().clone()
-            ),
+            transceiver,
+            neighbour_table: NeighbourTable::new(self.discovery_latency),
+            custody_transfers: CustodyTransfers::new(self.custody_retransmission_timeout),
+            ltp_engine: self.ltp.clone().map(LtpEngine::new),
+            oracle_access: self.oracle_access,
         }
     }
 }
\ No newline at end of file
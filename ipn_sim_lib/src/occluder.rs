@@ -0,0 +1,15 @@
+use downcast_rs::{impl_downcast, Downcast};
+use dyn_clonable::clonable;
+
+use crate::node::node::Node;
+
+/// An obstruction, other than a body, that can block the line of sight between two nodes at
+/// their current positions.
+#[clonable]
+pub trait Occluder: Clone + Downcast {
+    /// `held_nodes` are nodes besides the two tested that the caller has borrowed mutably, for
+    /// occluders that depend on the state of other nodes.
+    fn occludes(&self, transmitting_node: &Node, receiving_node: &Node, held_nodes: &[&Node]) -> bool;
+}
+
+impl_downcast!(Occluder);
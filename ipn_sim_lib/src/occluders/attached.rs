@@ -0,0 +1,89 @@
+use cgmath::{InnerSpace, Vector3};
+
+use crate::node::node::Node;
+use crate::occluder::Occluder;
+use crate::utils::{NodeId, Shared, SpaceMetric};
+
+/// A sphere carried by a node at an offset from it, such as a spacecraft's bus or solar array
+/// shadowing other spacecraft in a formation. Links of the carrying node itself are not blocked.
+#[derive(Clone)]
+pub struct AttachedOccluder {
+    node: Shared<Node>,
+    node_id: NodeId,
+    pub offset: Vector3<SpaceMetric>,
+    pub radius: SpaceMetric,
+}
+
+impl AttachedOccluder {
+    pub fn new(node: &Shared<Node>, offset: Vector3<SpaceMetric>, radius: SpaceMetric) -> Self {
+        Self {
+            node: node.clone(),
+            node_id: node.borrow().id,
+            offset,
+            radius,
+        }
+    }
+}
+
+impl Occluder for AttachedOccluder {
+    fn occludes(&self, transmitting_node: &Node, receiving_node: &Node, held_nodes: &[&Node]) -> bool {
+        if transmitting_node.id == self.node_id || receiving_node.id == self.node_id {
+            return false;
+        }
+        let position = match held_nodes.iter().find(|node| node.id == self.node_id) {
+            Some(node) => node.position,
+            None => self.node
+                .try_borrow()
+                .unwrap_or_else(|_| panic!("node {} carrying an occluder is borrowed but was not passed in", self.node_id))
+                .position,
+        };
+        let centre = position + self.offset;
+
+        let from = transmitting_node.position - centre;
+        let direction = receiving_node.position - transmitting_node.position;
+        let length2 = direction.magnitude2();
+        if length2 == 0. {
+            return false;
+        }
+        let closest = (-from.dot(direction) / length2).clamp(0., 1.);
+        (from + direction * closest).magnitude2() < self.radius.powi(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Point3, Vector3};
+
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::node::node_builder::NodeBuilder;
+    use crate::occluders::attached::AttachedOccluder;
+    use crate::routers::test_router::TestRouter;
+    use crate::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
+
+    #[test]
+    fn test_attached_occluder_blocks_other_links() {
+        let mut builder = IpnSimBuilder::new(0);
+        for &position in &[Point3::new(0., 0., 0.), Point3::new(10., 0., 0.), Point3::new(5., 5., 0.)] {
+            builder = builder.add_node(NodeBuilder::new()
+                .name("node")
+                .movement(StaticMovement::new(position))
+                .message_buffer_size(1)
+                .router(TestRouter)
+                .transceive_speed(1.)
+                .transceive_guard(SimpleTransceiveGuard::new(1e12)));
+        }
+        // The third node carries a sphere between the first two.
+        let occluder = AttachedOccluder::new(&builder.get_node(2), Vector3::new(0., -5., 0.), 1.);
+        let sim = builder.add_occluder(occluder).build();
+        let (first, second, carrier) = (sim.get_node(0), sim.get_node(1), sim.get_node(2));
+
+        assert!(!sim.nodes_are_covisible(&first.borrow(), &second.borrow(), &[]));
+        assert!(sim.nodes_are_covisible(&first.borrow(), &carrier.borrow(), &[]));
+
+        // A carrier held elsewhere is looked up among the held nodes, at its current position.
+        let mut held_carrier = carrier.borrow_mut();
+        held_carrier.position = Point3::new(5., 10., 0.);
+        assert!(sim.nodes_are_covisible(&first.borrow(), &second.borrow(), &[&*held_carrier]));
+    }
+}
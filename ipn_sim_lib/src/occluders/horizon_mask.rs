@@ -0,0 +1,134 @@
+use std::f64::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::movements::surface_movement::SurfaceMovement;
+use crate::node::node::Node;
+use crate::occluder::Occluder;
+use crate::utils::{NodeId, SpaceMetric};
+
+/// Terrain around a surface node, given as the elevation of the horizon at azimuths clockwise
+/// from north, in radians. The horizon is interpolated linearly between azimuths.
+#[derive(Clone)]
+pub struct HorizonMaskOccluder {
+    pub node_id: NodeId,
+    horizon: Vec<(SpaceMetric, SpaceMetric)>,
+}
+
+impl HorizonMaskOccluder {
+    pub fn new(node_id: NodeId, mut horizon: Vec<(SpaceMetric, SpaceMetric)>) -> Self {
+        assert!(!horizon.is_empty(), "horizon mask needs at least one point");
+        for (azimuth, _) in &mut horizon {
+            *azimuth = azimuth.rem_euclid(2. * PI);
+        }
+        horizon.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        Self { node_id, horizon }
+    }
+
+    pub fn horizon_elevation(&self, azimuth: SpaceMetric) -> SpaceMetric {
+        let azimuth = azimuth.rem_euclid(2. * PI);
+        let index = self.horizon.partition_point(|(point_azimuth, _)| *point_azimuth <= azimuth);
+        let (before, after) = match index {
+            0 => (self.horizon[self.horizon.len() - 1], self.horizon[0]),
+            index if index == self.horizon.len() => (self.horizon[index - 1], self.horizon[0]),
+            index => (self.horizon[index - 1], self.horizon[index]),
+        };
+        // Wrap around north between the last and first points.
+        let span = (after.0 - before.0).rem_euclid(2. * PI);
+        if span == 0. {
+            return before.1;
+        }
+        before.1 + (after.1 - before.1) * (azimuth - before.0).rem_euclid(2. * PI) / span
+    }
+
+    /// Azimuth and elevation of `other_node` seen from `node`, if `node` is on a surface.
+    pub fn azimuth_and_elevation(node: &Node, other_node: &Node) -> Option<(SpaceMetric, SpaceMetric)> {
+        let surface_movement = node.movement.downcast_ref::<SurfaceMovement>()?;
+        let body = surface_movement.body().borrow();
+        let up = (node.position - body.position).normalize();
+        let pole = body.rotation.polar_axis();
+        let north = match pole - up * pole.dot(up) {
+            north if north.magnitude2() > 0. => north.normalize(),
+            _ => Vector3::unit_x(),
+        };
+        let east = north.cross(up);
+
+        let direction = (other_node.position - node.position).normalize();
+        Some((
+            direction.dot(east).atan2(direction.dot(north)),
+            direction.dot(up).clamp(-1., 1.).asin(),
+        ))
+    }
+}
+
+impl Occluder for HorizonMaskOccluder {
+    fn occludes(&self, transmitting_node: &Node, receiving_node: &Node, _held_nodes: &[&Node]) -> bool {
+        let (node, other_node) = if transmitting_node.id == self.node_id {
+            (transmitting_node, receiving_node)
+        } else if receiving_node.id == self.node_id {
+            (receiving_node, transmitting_node)
+        } else {
+            return false;
+        };
+        Self::azimuth_and_elevation(node, other_node)
+            .is_some_and(|(azimuth, elevation)| elevation < self.horizon_elevation(azimuth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use cgmath::{EuclideanSpace, Point3};
+
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::movements::surface_movement::SurfaceMovement;
+    use crate::node::node_builder::NodeBuilder;
+    use crate::occluders::horizon_mask::HorizonMaskOccluder;
+    use crate::routers::test_router::TestRouter;
+    use crate::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
+
+    #[test]
+    fn test_horizon_elevation() {
+        let mask = HorizonMaskOccluder::new(0, vec![(PI / 2., 0.3), (2. * PI, 0.1)]);
+        assert!((mask.horizon_elevation(PI / 4.) - 0.2).abs() < 1e-12);
+        // Past the last point the horizon wraps around north back to the first.
+        assert!((mask.horizon_elevation(PI) - (0.3 - 0.2 / 3.)).abs() < 1e-12);
+        assert!((mask.horizon_elevation(-PI / 4.) - (0.3 - 0.2 * 5. / 6.)).abs() < 1e-12);
+        assert_eq!(HorizonMaskOccluder::new(0, vec![(1., 0.2)]).horizon_elevation(4.), 0.2);
+    }
+
+    #[test]
+    fn test_horizon_mask_occludes_low_links() {
+        let builder = IpnSimBuilder::new(0).add_body("Planet", 1e24, StaticMovement::new(Point3::origin()), 1e6);
+        let station = SurfaceMovement::new(&builder.get_body(0), 0., 0., 0.);
+        // A ridge to the north, open to the south.
+        let mut builder = builder.add_occluder(HorizonMaskOccluder::new(0, vec![(0., 0.5), (PI, 0.)]));
+
+        let node = |movement| {
+            let mut node_builder = NodeBuilder::new();
+            node_builder
+                .name("node")
+                .boxed_movement(movement)
+                .message_buffer_size(1)
+                .router(TestRouter)
+                .transceive_speed(1.)
+                .transceive_guard(SimpleTransceiveGuard::new(1e12));
+            node_builder
+        };
+        builder = builder.add_node(&node(Box::new(station)));
+        // Low to the north, overhead and low to the south.
+        for &position in &[Point3::new(1.001e6, 1e6, 0.), Point3::new(3e6, 0., 0.), Point3::new(1.001e6, -1e6, 0.)] {
+            builder = builder.add_node(&node(Box::new(StaticMovement::new(position))));
+        }
+
+        let sim = builder.build();
+        let covisible = |a: u16, b: u16| sim.nodes_are_covisible(&sim.get_node(a).borrow(), &sim.get_node(b).borrow(), &[]);
+        assert!(!covisible(0, 1));
+        assert!(!covisible(1, 0));
+        assert!(covisible(0, 2));
+        assert!(covisible(0, 3));
+        assert!(covisible(1, 3));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod attached;
+pub mod horizon_mask;
+pub mod ring;
@@ -0,0 +1,86 @@
+use cgmath::InnerSpace;
+
+use crate::node::node::Node;
+use crate::occluder::Occluder;
+use crate::utils::{Shared, SpaceMetric};
+
+/// An opaque planetary ring in the equatorial plane of a body, between two distances from its
+/// centre.
+#[derive(Clone)]
+pub struct RingOccluder {
+    body: Shared<crate::body::Body>,
+    pub inner_radius: SpaceMetric,
+    pub outer_radius: SpaceMetric,
+}
+
+impl RingOccluder {
+    pub fn new(body: &Shared<crate::body::Body>, inner_radius: SpaceMetric, outer_radius: SpaceMetric) -> Self {
+        Self {
+            body: body.clone(),
+            inner_radius,
+            outer_radius,
+        }
+    }
+}
+
+impl Occluder for RingOccluder {
+    fn occludes(&self, transmitting_node: &Node, receiving_node: &Node, _held_nodes: &[&Node]) -> bool {
+        let body = self.body.borrow();
+        let normal = body.rotation.polar_axis();
+        let from = transmitting_node.position - body.position;
+        let to = receiving_node.position - body.position;
+        let (from_height, to_height) = (from.dot(normal), to.dot(normal));
+
+        // Only lines crossing the ring plane between their ends can be blocked.
+        if from_height * to_height >= 0. {
+            return false;
+        }
+        let crossing = from + (to - from) * (from_height / (from_height - to_height));
+        (self.inner_radius..=self.outer_radius).contains(&crossing.magnitude())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{EuclideanSpace, Point3};
+
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::node::node_builder::NodeBuilder;
+    use crate::occluders::ring::RingOccluder;
+    use crate::routers::test_router::TestRouter;
+    use crate::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
+
+    #[test]
+    fn test_ring_occludes_crossing_links() {
+        let mut builder = IpnSimBuilder::new(0).add_body("Planet", 1e24, StaticMovement::new(Point3::origin()), 1e6);
+        let ring = RingOccluder::new(&builder.get_body(0), 2e6, 3e6);
+        builder = builder.add_occluder(ring);
+        // Pairs of nodes either side of the ring plane, crossing it inside, across and outside
+        // the ring, and a pair on the same side.
+        let pairs = [
+            (Point3::new(1.5e6, 1e6, 0.), Point3::new(1.5e6, -1e6, 0.)),
+            (Point3::new(2.5e6, 1e6, 0.), Point3::new(2.5e6, -1e6, 0.)),
+            (Point3::new(4e6, 1e6, 0.), Point3::new(4e6, -1e6, 0.)),
+            (Point3::new(2.5e6, 1e6, 0.), Point3::new(-2.5e6, 2e6, 0.)),
+        ];
+        for &(from, to) in &pairs {
+            for &position in &[from, to] {
+                builder = builder.add_node(NodeBuilder::new()
+                    .name("node")
+                    .movement(StaticMovement::new(position))
+                    .message_buffer_size(1)
+                    .router(TestRouter)
+                    .transceive_speed(1.)
+                    .transceive_guard(SimpleTransceiveGuard::new(1e12)));
+            }
+        }
+
+        let sim = builder.build();
+        for (index, &covisible) in [true, false, true, true].iter().enumerate() {
+            let from = sim.get_node(2 * index as u16);
+            let to = sim.get_node(2 * index as u16 + 1);
+            assert_eq!(sim.nodes_are_covisible(&from.borrow(), &to.borrow(), &[]), covisible);
+        }
+    }
+}
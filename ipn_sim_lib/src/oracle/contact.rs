@@ -0,0 +1,135 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use rustc_hash::FxHashMap;
+
+use crate::utils::{NodeId, TimeMetric};
+
+/// A window during which two nodes can transceive, in both directions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contact {
+    pub node_ids: (NodeId, NodeId),
+    pub start: TimeMetric,
+    pub end: TimeMetric,
+    pub light_time: TimeMetric,
+}
+
+impl Contact {
+    pub fn other(&self, node_id: NodeId) -> Option<NodeId> {
+        if self.node_ids.0 == node_id {
+            Some(self.node_ids.1)
+        } else if self.node_ids.1 == node_id {
+            Some(self.node_ids.0)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hop {
+    pub node_id: NodeId,
+    pub departure_time: TimeMetric,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Route {
+    pub hops: Vec<Hop>,
+    pub arrival_time: TimeMetric,
+}
+
+/// Finds the route over `contacts` arriving at `destination_id` soonest, departing `source_id` no
+/// earlier than `start_time`. Each hop departs as soon as both the data and the contact are
+/// available and arrives one light time later.
+pub fn earliest_arrival(
+    contacts: &[Contact],
+    source_id: NodeId,
+    destination_id: NodeId,
+    start_time: TimeMetric,
+) -> Option<Route> {
+    let mut arrival_times = FxHashMap::default();
+    let mut previous_hops: FxHashMap<NodeId, (NodeId, TimeMetric)> = FxHashMap::default();
+    let mut queue = BinaryHeap::new();
+
+    arrival_times.insert(source_id, start_time);
+    queue.push(Reverse((start_time, source_id)));
+
+    while let Some(Reverse((arrival_time, node_id))) = queue.pop() {
+        if arrival_time > arrival_times[&node_id] {
+            continue;
+        }
+        if node_id == destination_id {
+            let mut hops = vec![];
+            let mut hop_node_id = destination_id;
+            while let Some(&(previous_node_id, departure_time)) = previous_hops.get(&hop_node_id) {
+                hops.push(Hop {
+                    node_id: hop_node_id,
+                    departure_time,
+                });
+                hop_node_id = previous_node_id;
+            }
+            hops.reverse();
+            return Some(Route {
+                hops,
+                arrival_time,
+            });
+        }
+
+        for contact in contacts {
+            if contact.end < arrival_time {
+                continue;
+            }
+            if let Some(other_node_id) = contact.other(node_id) {
+                let departure_time = arrival_time.max(contact.start);
+                let other_arrival_time = departure_time + contact.light_time;
+                let improved = arrival_times
+                    .get(&other_node_id)
+                    .is_none_or(|&known_arrival_time| other_arrival_time < known_arrival_time);
+                if improved {
+                    arrival_times.insert(other_node_id, other_arrival_time);
+                    previous_hops.insert(other_node_id, (node_id, departure_time));
+                    queue.push(Reverse((other_arrival_time, other_node_id)));
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::oracle::contact::{earliest_arrival, Contact, Hop};
+
+    fn contact(node_ids: (u16, u16), start: u64, end: u64) -> Contact {
+        Contact {
+            node_ids,
+            start,
+            end,
+            light_time: 1,
+        }
+    }
+
+    #[test]
+    fn test_earliest_arrival() {
+        let contacts = vec![
+            contact((0, 1), 10, 20),
+            contact((1, 2), 5, 8),
+            contact((1, 2), 30, 40),
+            contact((0, 3), 0, 5),
+            contact((3, 2), 15, 16),
+        ];
+
+        let route = earliest_arrival(&contacts, 0, 2, 0).unwrap();
+        assert_eq!(route.arrival_time, 16);
+        assert_eq!(route.hops, vec![
+            Hop { node_id: 3, departure_time: 0 },
+            Hop { node_id: 2, departure_time: 15 },
+        ]);
+
+        let route = earliest_arrival(&contacts, 0, 2, 6).unwrap();
+        assert_eq!(route.arrival_time, 31);
+        assert_eq!(route.hops[0], Hop { node_id: 1, departure_time: 10 });
+
+        assert_eq!(earliest_arrival(&contacts, 0, 2, 41), None);
+    }
+}
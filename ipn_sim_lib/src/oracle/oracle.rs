@@ -0,0 +1,151 @@
+use std::rc::Rc;
+
+use cgmath::MetricSpace;
+use rustc_hash::FxHashMap;
+
+use crate::body::Body;
+use crate::ipn_sim::ipn_sim::IpnSim;
+use crate::node::message_buffer::MessageBuffer;
+use crate::node::node::Node;
+use crate::oracle::contact::Contact;
+use crate::utils::{MessageId, NodeId, Shared, TimeMetric, C};
+
+/// Contacts between every pair of nodes, sampled from `start` until the end of the simulation.
+pub struct ContactPlan {
+    pub start: TimeMetric,
+    pub resolution: TimeMetric,
+    pub contacts: Vec<Contact>,
+}
+
+/// Omniscient access to the simulation for benchmark routers. Only available to nodes built with
+/// [`NodeBuilder::oracle_access`](crate::node::node_builder::NodeBuilder::oracle_access).
+pub struct Oracle<'a> {
+    node: &'a mut Node,
+    node_rc: &'a Shared<Node>,
+    sim: &'a mut IpnSim,
+}
+
+impl<'a> Oracle<'a> {
+    pub fn new(node: &'a mut Node, node_rc: &'a Shared<Node>, sim: &'a mut IpnSim) -> Self {
+        Self {
+            node,
+            node_rc,
+            sim,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.sim.nodes.as_ref().unwrap().len()
+    }
+
+    /// Contacts that have not yet ended, found by sampling every `resolution` until the end of the
+    /// simulation. The plan is computed once per simulation and shared by all oracle routers.
+    pub fn future_contacts(&mut self, resolution: TimeMetric) -> Vec<Contact> {
+        let time = self.sim.time;
+        let cached = self.sim.contact_plan
+            .as_ref()
+            .is_some_and(|contact_plan| contact_plan.start <= time && contact_plan.resolution == resolution);
+
+        if !cached {
+            let contacts = self.sample_contacts(time, resolution);
+            self.sim.contact_plan = Some(ContactPlan {
+                start: time,
+                resolution,
+                contacts,
+            });
+        }
+
+        self.sim.contact_plan
+            .as_ref()
+            .unwrap()
+            .contacts
+            .iter()
+            .filter(|contact| contact.end >= time)
+            .cloned()
+            .collect()
+    }
+
+    /// Calls `f` with the message buffer of any node.
+    pub fn with_message_buffer<T>(&self, node_id: NodeId, f: impl FnOnce(&MessageBuffer) -> T) -> T {
+        if node_id == self.node.id {
+            f(&self.node.message_buffer)
+        } else {
+            f(&self.sim.get_node(node_id).borrow().message_buffer)
+        }
+    }
+
+    pub fn delivered_to(&self, message_id: MessageId) -> &[NodeId] {
+        self.sim.delivered_messages
+            .get(&message_id)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn is_delivered(&self, message_id: MessageId) -> bool {
+        !self.delivered_to(message_id).is_empty()
+    }
+
+    fn sample_contacts(&mut self, start: TimeMetric, resolution: TimeMetric) -> Vec<Contact> {
+        let Self { node, node_rc, sim } = self;
+        let nodes = sim.nodes.take().unwrap();
+
+        let mut contacts = vec![];
+        let mut open_contacts: FxHashMap<(usize, usize), Contact> = FxHashMap::default();
+
+        let mut time = start;
+        while time <= sim.length {
+            set_world_time(&sim.bodies, &nodes, node, node_rc, time);
+
+            for (first_index, first_node) in nodes.iter().enumerate() {
+                for (second_index, second_node) in nodes.iter().enumerate().skip(first_index + 1) {
+                    let first_ref = (!Rc::ptr_eq(first_node, node_rc)).then(|| first_node.borrow());
+                    let second_ref = (!Rc::ptr_eq(second_node, node_rc)).then(|| second_node.borrow());
+                    let first_node = first_ref.as_deref().unwrap_or(&**node);
+                    let second_node = second_ref.as_deref().unwrap_or(&**node);
+
+                    let key = (first_index, second_index);
+                    if sim.nodes_can_transceive_holding(first_node, second_node, &[&**node]) {
+                        open_contacts
+                            .entry(key)
+                            .or_insert_with(|| Contact {
+                                node_ids: (first_node.id, second_node.id),
+                                start: time,
+                                end: time,
+                                light_time: (first_node.position.distance(second_node.position) / C) as TimeMetric,
+                            })
+                            .end = time;
+                    } else if let Some(contact) = open_contacts.remove(&key) {
+                        contacts.push(contact);
+                    }
+                }
+            }
+
+            time += resolution;
+        }
+        contacts.extend(open_contacts.into_values());
+
+        set_world_time(&sim.bodies, &nodes, node, node_rc, sim.time);
+        sim.nodes = Some(nodes);
+
+        contacts
+    }
+}
+
+/// Moves every body and node to where they are at `time`.
+fn set_world_time(
+    bodies: &[Shared<Body>],
+    nodes: &[Shared<Node>],
+    node: &mut Node,
+    node_rc: &Shared<Node>,
+    time: TimeMetric,
+) {
+    for body in bodies {
+        body.borrow_mut().set_position(time);
+    }
+    for other_node in nodes {
+        if Rc::ptr_eq(other_node, node_rc) {
+            node.set_position(time);
+        } else {
+            other_node.borrow_mut().set_position(time);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::any::{type_name, Any};
+use std::rc::Rc;
+
+use crate::binary_serde::BinarySerde;
+use crate::utils::Data;
+
+/// A typed unit of router to router communication.
+///
+/// Packets are serialized once when sent and decoded by every receiving router. Packets that opt
+/// in to sharing skip decoding entirely: receivers are handed a clone of the sent value instead.
+pub trait Packet: BinarySerde + Clone + 'static {
+    /// Decoded packets are kept alongside their bytes and reused by every receiver.
+    const SHARED: bool = false;
+
+    /// Name used to group transmitted bytes in reports.
+    fn packet_type(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    /// Nominal size of the packet on the air. Packets without one are sized by their serialized
+    /// bytes, all of which are counted as header.
+    fn packet_size(&self) -> Option<PacketSize> {
+        None
+    }
+}
+
+/// On-air size of a packet, split between protocol overhead and carried user payload.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PacketSize {
+    pub header: usize,
+    pub payload: usize,
+}
+
+impl PacketSize {
+    pub fn new(header: usize, payload: usize) -> Self {
+        Self { header, payload }
+    }
+
+    pub fn header(header: usize) -> Self {
+        Self::new(header, 0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.header + self.payload
+    }
+}
+
+/// A packet as it travels through the transceiver and over the air.
+#[derive(Clone)]
+pub struct PacketData {
+    pub data: Data,
+    pub packet_type: Option<&'static str>,
+    pub size: PacketSize,
+    decoded: Option<Rc<dyn Any>>,
+}
+
+impl PacketData {
+    pub fn raw(data: Data) -> Self {
+        Self {
+            size: PacketSize::header(data.len()),
+            data,
+            packet_type: None,
+            decoded: None,
+        }
+    }
+
+    pub fn from_packet<P: Packet>(packet: P) -> Self {
+        let data = packet.as_data();
+        Self {
+            size: packet
+                .packet_size()
+                .unwrap_or_else(|| PacketSize::header(data.len())),
+            data,
+            packet_type: Some(packet.packet_type()),
+            decoded: if P::SHARED {
+                Some(Rc::new(packet))
+            } else {
+                None
+            },
+        }
+    }
+
+    pub fn decode<P: Packet>(&self) -> bincode::Result<P> {
+        if let Some(packet) = self
+            .decoded
+            .as_ref()
+            .and_then(|decoded| decoded.downcast_ref::<P>())
+        {
+            return Ok(packet.clone());
+        }
+        P::try_from_data(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::binary_serde::BinarySerde;
+    use crate::packet::{Packet, PacketData, PacketSize};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct TestPacket(u32);
+
+    impl BinarySerde for TestPacket {}
+
+    impl Packet for TestPacket {}
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct SharedTestPacket(u32);
+
+    impl BinarySerde for SharedTestPacket {}
+
+    impl Packet for SharedTestPacket {
+        const SHARED: bool = true;
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let packet_data = PacketData::from_packet(TestPacket(42));
+        assert_eq!(packet_data.decode::<TestPacket>().unwrap(), TestPacket(42));
+
+        let packet_data = PacketData::from_packet(SharedTestPacket(42));
+        assert_eq!(packet_data.decode::<SharedTestPacket>().unwrap(), SharedTestPacket(42));
+    }
+
+    #[test]
+    fn test_size() {
+        let packet_data = PacketData::from_packet(TestPacket(42));
+        assert_eq!(packet_data.size, PacketSize::header(packet_data.data.len()));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let packet_data = PacketData::raw(vec![1].into_boxed_slice());
+        assert!(packet_data.decode::<TestPacket>().is_err());
+    }
+}
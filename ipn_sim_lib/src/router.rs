@@ -2,6 +2,10 @@ use crate::router_link::RouterLink;
 use crate::utils::{Data, NodeId, TimeMetric};
 use dyn_clonable::clonable;
 use crate::message_destination::MessageDestination;
+use crate::packet::{Packet, PacketData};
+
+pub mod components;
+pub mod layered;
 
 #[clonable]
 pub trait Router: Clone {
@@ -18,5 +22,61 @@ pub trait Router: Clone {
 
     fn on_data_received(&mut self, _link: &mut RouterLink, _data: Data) {}
 
+    fn on_packet_data_received(&mut self, link: &mut RouterLink, packet_data: PacketData) {
+        self.on_data_received(link, packet_data.data)
+    }
+
     fn on_awake(&mut self, link: &mut RouterLink) {}
 }
+
+/// A router that communicates with a single typed [`Packet`], decoded by the engine before it is
+/// handed over.
+pub trait PacketRouter: Clone {
+    type Packet: Packet;
+
+    fn on_init(&mut self, _link: &mut RouterLink, _id: NodeId) {}
+
+    fn on_message_created(
+        &mut self,
+        _link: &mut RouterLink,
+        _destination: MessageDestination<NodeId>,
+        _payload: Data,
+        _ttl: Option<TimeMetric>,
+    ) {
+    }
+
+    fn on_packet_received(&mut self, _link: &mut RouterLink, _packet: Self::Packet) {}
+
+    fn on_malformed_packet(&mut self, link: &mut RouterLink, error: bincode::Error) {
+        link.log(format!("dropped malformed packet: {}", error));
+    }
+
+    fn on_awake(&mut self, _link: &mut RouterLink) {}
+}
+
+impl<R: PacketRouter> Router for R {
+    fn on_init(&mut self, link: &mut RouterLink, id: NodeId) {
+        PacketRouter::on_init(self, link, id)
+    }
+
+    fn on_message_created(
+        &mut self,
+        link: &mut RouterLink,
+        destination: MessageDestination<NodeId>,
+        payload: Data,
+        ttl: Option<TimeMetric>,
+    ) {
+        PacketRouter::on_message_created(self, link, destination, payload, ttl)
+    }
+
+    fn on_packet_data_received(&mut self, link: &mut RouterLink, packet_data: PacketData) {
+        match packet_data.decode::<R::Packet>() {
+            Ok(packet) => self.on_packet_received(link, packet),
+            Err(error) => self.on_malformed_packet(link, error),
+        }
+    }
+
+    fn on_awake(&mut self, link: &mut RouterLink) {
+        PacketRouter::on_awake(self, link)
+    }
+}
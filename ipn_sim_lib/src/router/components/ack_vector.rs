@@ -0,0 +1,48 @@
+use bit_vec::BitVec;
+
+use crate::router::components::bloom_filter::{BloomFilter, BloomParams};
+use crate::router::components::message_store::MessageStore;
+use crate::router_link::RouterLink;
+use crate::utils::MessageId;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Ack {
+    None,
+    Unilateral,
+    Bilateral,
+}
+
+/// Summary of messages known to have been delivered, propagated between neighbours so delivered
+/// messages can be purged from buffers.
+#[derive(Clone)]
+pub struct AckVector {
+    acked: BloomFilter,
+}
+
+impl AckVector {
+    pub fn new(params: impl Into<BloomParams>) -> Self {
+        Self {
+            acked: BloomFilter::new(params),
+        }
+    }
+
+    pub fn acknowledge(&mut self, message_id: MessageId) {
+        self.acked.insert(message_id);
+    }
+
+    pub fn bits(&self) -> &BitVec {
+        self.acked.bits()
+    }
+
+    /// Purges every stored message acknowledged by a neighbour.
+    pub fn merge<S>(&mut self, link: &mut RouterLink, store: &mut MessageStore<S>, other: &BitVec) {
+        if other.any() {
+            for id in store.contained_in(other) {
+                let stored_message = store.remove(link, id).unwrap();
+                self.acknowledge(stored_message.id);
+
+                link.report_message_dropped(stored_message.id);
+            }
+        }
+    }
+}
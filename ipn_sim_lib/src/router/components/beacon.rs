@@ -0,0 +1,36 @@
+use rand::random;
+
+use crate::router_link::RouterLink;
+use crate::utils::TimeMetric;
+
+/// Periodic neighbour discovery. Beacons are spaced at least `interval` apart and the router is
+/// woken each time one becomes due.
+#[derive(Clone)]
+pub struct Beacon {
+    interval: TimeMetric,
+    last_beacon_time: Option<TimeMetric>,
+}
+
+impl Beacon {
+    pub fn new(interval: TimeMetric) -> Self {
+        Self {
+            interval,
+            last_beacon_time: None,
+        }
+    }
+
+    /// Staggers the first beacon so nodes do not all announce themselves at once.
+    pub fn on_init(&self, link: &mut RouterLink) {
+        link.sleep_for(random::<TimeMetric>() % self.interval)
+    }
+
+    pub fn is_due(&self, link: &RouterLink) -> bool {
+        self.last_beacon_time
+            .is_none_or(|last_beacon_time| last_beacon_time + self.interval <= link.get_time())
+    }
+
+    pub fn mark_sent(&mut self, link: &mut RouterLink) {
+        self.last_beacon_time = Some(link.get_time());
+        link.sleep_for(self.interval);
+    }
+}
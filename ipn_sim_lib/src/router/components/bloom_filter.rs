@@ -0,0 +1,171 @@
+use std::collections::hash_map::DefaultHasher;
+use std::f64::consts::LN_2;
+use std::hash::Hasher;
+
+use bit_vec::BitVec;
+
+use crate::utils::MessageId;
+
+/// Size in bits and number of hash functions of a Bloom filter. Neighbours exchanging filters
+/// must agree on these.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BloomParams {
+    pub size: usize,
+    pub hashes: usize,
+}
+
+impl BloomParams {
+    pub fn new(size: usize, hashes: usize) -> Self {
+        assert!(size > 0 && hashes > 0);
+        Self { size, hashes }
+    }
+
+    /// Sizes a filter to hold `expected_items` with at most `false_positive_rate` false
+    /// positives, using the optimal bit count and hash count.
+    pub fn for_false_positive_rate(expected_items: usize, false_positive_rate: f64) -> Self {
+        assert!(false_positive_rate > 0. && false_positive_rate < 1.);
+        let expected_items = expected_items.max(1) as f64;
+        let size = (-expected_items * false_positive_rate.ln() / (LN_2 * LN_2)).ceil();
+        let hashes = (size / expected_items * LN_2).round().max(1.);
+        Self::new(size as usize, hashes as usize)
+    }
+
+    /// Expected false positive rate once `items` message ids have been inserted.
+    pub fn false_positive_rate(&self, items: usize) -> f64 {
+        let k = self.hashes as f64;
+        (1. - (-k * items as f64 / self.size as f64).exp()).powf(k)
+    }
+
+    /// Bit indices of a message id, derived from a single 64 bit hash by double hashing.
+    pub fn indices(&self, message_id: MessageId) -> impl Iterator<Item = usize> {
+        let mut hasher = DefaultHasher::default();
+        hasher.write(&message_id.to_le_bytes());
+        let hash = hasher.finish();
+        let h1 = hash & 0xFFFF_FFFF;
+        let h2 = (hash >> 32) | 1;
+        let size = self.size as u64;
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % size) as usize)
+    }
+
+    /// Whether a message id is in a filter received from a neighbour.
+    pub fn contains_in(&self, bits: &BitVec, message_id: MessageId) -> bool {
+        self.indices(message_id).all(|index| bits.get(index).unwrap_or(false))
+    }
+}
+
+/// A single hash summary vector of `size` bits, as used before filters were configurable.
+impl From<usize> for BloomParams {
+    fn from(size: usize) -> Self {
+        Self::new(size, 1)
+    }
+}
+
+/// Set membership of message ids with false positives but no false negatives.
+#[derive(Clone)]
+pub struct BloomFilter {
+    params: BloomParams,
+    bits: BitVec,
+}
+
+impl BloomFilter {
+    pub fn new(params: impl Into<BloomParams>) -> Self {
+        let params = params.into();
+        Self {
+            params,
+            bits: BitVec::from_elem(params.size, false),
+        }
+    }
+
+    pub fn params(&self) -> BloomParams {
+        self.params
+    }
+
+    pub fn insert(&mut self, message_id: MessageId) {
+        for index in self.params.indices(message_id) {
+            self.bits.set(index, true);
+        }
+    }
+
+    pub fn contains(&self, message_id: MessageId) -> bool {
+        self.params.contains_in(&self.bits, message_id)
+    }
+
+    pub fn bits(&self) -> &BitVec {
+        &self.bits
+    }
+}
+
+/// A Bloom filter with a counter per bit so ids can be removed again. Only the bits, set where
+/// the counter is non zero, go on the wire.
+#[derive(Clone)]
+pub struct CountingBloomFilter {
+    filter: BloomFilter,
+    counters: Vec<u8>,
+}
+
+impl CountingBloomFilter {
+    pub fn new(params: impl Into<BloomParams>) -> Self {
+        let filter = BloomFilter::new(params);
+        Self {
+            counters: vec![0; filter.params.size],
+            filter,
+        }
+    }
+
+    pub fn params(&self) -> BloomParams {
+        self.filter.params
+    }
+
+    pub fn insert(&mut self, message_id: MessageId) {
+        for index in self.filter.params.indices(message_id) {
+            self.counters[index] = self.counters[index].saturating_add(1);
+            self.filter.bits.set(index, true);
+        }
+    }
+
+    /// Removes an id previously inserted. Saturated counters are never decremented, so removal
+    /// can not introduce false negatives.
+    pub fn remove(&mut self, message_id: MessageId) {
+        for index in self.filter.params.indices(message_id) {
+            match self.counters[index] {
+                0 | u8::MAX => {}
+                count => {
+                    self.counters[index] = count - 1;
+                    self.filter.bits.set(index, count > 1);
+                }
+            }
+        }
+    }
+
+    pub fn contains(&self, message_id: MessageId) -> bool {
+        self.filter.contains(message_id)
+    }
+
+    pub fn bits(&self) -> &BitVec {
+        self.filter.bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::router::components::bloom_filter::{BloomParams, CountingBloomFilter};
+
+    #[test]
+    fn test_counting_filter_removes_without_false_negatives() {
+        let params = BloomParams::for_false_positive_rate(100, 0.01);
+        assert_eq!(params.hashes, 7);
+        assert!(params.false_positive_rate(100) < 0.011);
+
+        let mut filter = CountingBloomFilter::new(params);
+        for message_id in 0..100 {
+            filter.insert(message_id);
+        }
+        for message_id in 0..50 {
+            filter.remove(message_id);
+        }
+        assert!((50..100).all(|message_id| filter.contains(message_id)));
+
+        let false_positives = (1000..11000).filter(|&message_id| filter.contains(message_id)).count();
+        assert!(false_positives < 200);
+    }
+}
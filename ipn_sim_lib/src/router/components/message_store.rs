@@ -0,0 +1,150 @@
+use bit_vec::BitVec;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::node::message_buffer::MessageHandle;
+use crate::router::components::bloom_filter::BloomParams;
+use crate::router::components::summary_vector::SummaryVector;
+use crate::router_link::RouterLink;
+use crate::utils;
+use crate::utils::{Data, MessageId, NodeId, TimeMetric};
+
+#[derive(Clone)]
+pub struct StoredMessage<S> {
+    pub id: MessageId,
+    pub handle: MessageHandle,
+    pub ttl: Option<TimeMetric>,
+    pub state: S,
+}
+
+/// Tracks the messages a router holds in the node's message buffer, keyed by message id, along
+/// with per-copy forwarding state `S`. Also responsible for TTL eviction.
+#[derive(Clone)]
+pub struct MessageStore<S> {
+    summary_vector: SummaryVector,
+    messages: FxHashMap<MessageId, StoredMessage<S>>,
+    /// Stored messages already reported suppressed, with the neighbour they were suppressed for.
+    suppressions: FxHashSet<(MessageId, NodeId)>,
+    next_message_index: u16,
+}
+
+impl<S> MessageStore<S> {
+    pub fn new(summary_vector: impl Into<BloomParams>) -> Self {
+        Self {
+            summary_vector: SummaryVector::new(summary_vector),
+            messages: FxHashMap::default(),
+            suppressions: FxHashSet::default(),
+            next_message_index: 0,
+        }
+    }
+
+    pub fn next_message_id(&mut self, node_id: NodeId) -> MessageId {
+        utils::next_message_id(node_id, &mut self.next_message_index)
+    }
+
+    pub fn summary_vector(&self) -> &SummaryVector {
+        &self.summary_vector
+    }
+
+    pub fn has_seen(&self, message_id: MessageId) -> bool {
+        self.summary_vector.contains(message_id)
+    }
+
+    /// Advertises a message as held without storing it, e.g. once delivered.
+    pub fn mark_seen(&mut self, message_id: MessageId) {
+        self.summary_vector.insert(message_id);
+    }
+
+    /// Stores a message occupying `size` bytes of the buffer, returning false if it does not fit.
+    pub fn insert(
+        &mut self,
+        link: &mut RouterLink,
+        id: MessageId,
+        ttl: Option<TimeMetric>,
+        data: Data,
+        size: usize,
+        state: S,
+    ) -> bool {
+        if let Some(handle) = link.add_sized_to_message_buffer(data, size) {
+            self.messages.insert(id, StoredMessage {
+                id,
+                handle,
+                ttl,
+                state,
+            });
+            self.summary_vector.insert(id);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn get(&self, id: MessageId) -> Option<&StoredMessage<S>> {
+        self.messages.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: MessageId) -> Option<&mut StoredMessage<S>> {
+        self.messages.get_mut(&id)
+    }
+
+    pub fn ids(&self) -> Vec<MessageId> {
+        self.messages.keys().copied().collect()
+    }
+
+    /// Stored messages whose ids are in a neighbour's filter.
+    pub fn contained_in(&self, bits: &BitVec) -> Vec<MessageId> {
+        let params = self.summary_vector.params();
+        self.messages
+            .keys()
+            .filter(|&&id| params.contains_in(bits, id))
+            .copied()
+            .collect()
+    }
+
+    /// Stored messages whose ids are not in the summary vector of `neighbour_id`. The others are
+    /// reported as suppressed, once per neighbour while stored, since some of them may be false
+    /// positives of the filter.
+    pub fn missing_from(&mut self, link: &mut RouterLink, neighbour_id: NodeId, summary_vector: &BitVec) -> Vec<MessageId> {
+        let params = self.summary_vector.params();
+        let mut missing = vec![];
+        for &id in self.messages.keys() {
+            if params.contains_in(summary_vector, id) {
+                if self.suppressions.insert((id, neighbour_id)) {
+                    link.report_message_suppressed(id, neighbour_id);
+                }
+            } else {
+                missing.push(id);
+            }
+        }
+        missing
+    }
+
+    /// Removes a message from the buffer. It stays in the summary vector so it is not re-accepted,
+    /// unless the caller removes it as well, as `verify_ttl` does for expired messages.
+    pub fn remove(&mut self, link: &mut RouterLink, id: MessageId) -> Option<StoredMessage<S>> {
+        self.suppressions.retain(|&(message_id, _)| message_id != id);
+        self.messages.remove(&id).inspect(|stored_message| {
+            link.remove_from_message_buffer(&stored_message.handle);
+        })
+    }
+
+    /// Evicts the message if its TTL has passed, returning true if it was evicted.
+    pub fn verify_ttl(&mut self, link: &mut RouterLink, id: MessageId) -> bool {
+        let expired = self.messages
+            .get(&id)
+            .and_then(|stored_message| stored_message.ttl)
+            .is_some_and(|ttl| ttl <= link.get_time());
+
+        if expired {
+            let stored_message = self.remove(link, id).unwrap();
+            self.summary_vector.remove(stored_message.id);
+            link.report_message_dropped(stored_message.id);
+        }
+        expired
+    }
+
+    pub fn evict_expired(&mut self, link: &mut RouterLink) {
+        for id in self.ids() {
+            self.verify_ttl(link, id);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod ack_vector;
+pub mod beacon;
+pub mod bloom_filter;
+pub mod message_store;
+pub mod summary_vector;
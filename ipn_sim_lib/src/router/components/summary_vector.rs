@@ -0,0 +1,48 @@
+use bit_vec::BitVec;
+
+use crate::router::components::bloom_filter::{BloomParams, CountingBloomFilter};
+use crate::utils::MessageId;
+
+/// A counting Bloom filter of the message ids a router has seen, exchanged between neighbours so
+/// each can hand over the messages the other has not yet seen.
+#[derive(Clone)]
+pub struct SummaryVector {
+    filter: CountingBloomFilter,
+}
+
+impl SummaryVector {
+    pub fn new(params: impl Into<BloomParams>) -> Self {
+        Self {
+            filter: CountingBloomFilter::new(params),
+        }
+    }
+
+    pub fn params(&self) -> BloomParams {
+        self.filter.params()
+    }
+
+    pub fn insert(&mut self, message_id: MessageId) {
+        self.filter.insert(message_id);
+    }
+
+    pub fn remove(&mut self, message_id: MessageId) {
+        self.filter.remove(message_id);
+    }
+
+    pub fn contains(&self, message_id: MessageId) -> bool {
+        self.filter.contains(message_id)
+    }
+
+    /// Whether `other` has a bit set that is not set in this vector, meaning the neighbour holds
+    /// at least one message not seen here.
+    pub fn is_missing_from(&self, other: &BitVec) -> bool {
+        let mut missing = self.filter.bits().clone();
+        missing.negate();
+        missing.and(other);
+        missing.any()
+    }
+
+    pub fn bits(&self) -> &BitVec {
+        self.filter.bits()
+    }
+}
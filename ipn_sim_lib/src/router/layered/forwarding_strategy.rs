@@ -0,0 +1,31 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::ipn_sim::groups::Groups;
+use crate::router::layered::message::Message;
+use crate::utils::NodeId;
+use crate::wire_size::WireSize;
+
+/// Decides which messages a [`LayeredRouter`](crate::router::layered::layered_router::LayeredRouter)
+/// hands to neighbours that request them.
+pub trait ForwardingStrategy: Clone + 'static {
+    /// State carried by each copy of a message, e.g. the number of copies it may still spawn.
+    type CopyState: Serialize + DeserializeOwned + Clone + WireSize;
+
+    fn on_message_created(&mut self, message: &Message) -> Self::CopyState;
+
+    /// Called when a neighbour requests a held message. Returns the state of the copy to hand over,
+    /// or `None` to withhold it. `groups` resolves group destinations.
+    fn on_forward(
+        &mut self,
+        message: &Message,
+        state: &mut Self::CopyState,
+        neighbour_id: NodeId,
+        groups: &Groups,
+    ) -> Option<Self::CopyState>;
+
+    /// Whether the local copy is kept after being forwarded.
+    fn retain(&self, _state: &Self::CopyState) -> bool {
+        true
+    }
+}
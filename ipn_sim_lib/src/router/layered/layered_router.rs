@@ -0,0 +1,301 @@
+use bit_vec::BitVec;
+use rustc_hash::FxHashMap;
+
+use crate::binary_serde::BinarySerde;
+use crate::message_destination::MessageDestination;
+use crate::router::components::ack_vector::{Ack, AckVector};
+use crate::router::components::beacon::Beacon;
+use crate::router::components::bloom_filter::BloomParams;
+use crate::router::components::message_store::MessageStore;
+use crate::router::layered::forwarding_strategy::ForwardingStrategy;
+use crate::router::layered::message::Message;
+use crate::router::layered::packet::LayeredPacket;
+use crate::router::PacketRouter;
+use crate::router_link::RouterLink;
+use crate::utils::{Data, NodeId, TimeMetric};
+use crate::wire_size::WireSize;
+
+/// Which layers a [`LayeredRouter`] stacks on top of its message store.
+#[derive(Clone)]
+pub struct Layers {
+    pub summary_vector: BloomParams,
+    pub beacon_interval: TimeMetric,
+    pub ping_on_receive: bool,
+    pub ack: Ack,
+    pub bilateral_fulfillment: bool,
+}
+
+/// A summary vector anti-entropy router. Neighbours are discovered by beacons carrying the
+/// summary vector, a neighbour holding unseen messages is sent a request carrying the requester's
+/// own summary vector and answers with the messages not in it, acknowledgements optionally purge
+/// delivered messages, and the forwarding strategy decides which requested messages are handed
+/// over.
+#[derive(Clone)]
+pub struct LayeredRouter<S: ForwardingStrategy> {
+    beacon: Beacon,
+    store: MessageStore<S::CopyState>,
+    ack_vector: Option<AckVector>,
+    request_interval: Option<TimeMetric>,
+    last_request_times: FxHashMap<NodeId, TimeMetric>,
+    ping_on_receive: bool,
+    bilateral_ack: bool,
+    bilateral_fulfillment: bool,
+    keep_delivered: bool,
+    strategy: S,
+}
+
+impl<S: ForwardingStrategy> LayeredRouter<S> {
+    pub fn with_strategy(strategy: S, layers: Layers) -> Self {
+        Self {
+            beacon: Beacon::new(layers.beacon_interval),
+            store: MessageStore::new(layers.summary_vector),
+            ack_vector: (layers.ack != Ack::None).then(|| AckVector::new(layers.summary_vector)),
+            request_interval: None,
+            last_request_times: FxHashMap::default(),
+            ping_on_receive: layers.ping_on_receive,
+            bilateral_ack: layers.ack == Ack::Bilateral,
+            bilateral_fulfillment: layers.bilateral_fulfillment,
+            keep_delivered: false,
+            strategy,
+        }
+    }
+
+    /// Sends requests to the same neighbour at most once per `request_interval`.
+    pub fn throttle_requests(mut self, request_interval: TimeMetric) -> Self {
+        self.request_interval = Some(request_interval);
+        self
+    }
+
+    /// Stores messages delivered to this node and offers them onward like any other.
+    pub fn keep_delivered(mut self) -> Self {
+        self.keep_delivered = true;
+        self
+    }
+
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    fn insert_message(&mut self, link: &mut RouterLink, message: Message, state: S::CopyState) {
+        if self.store.has_seen(message.id) {
+            link.report_message_dropped(message.id);
+        } else if link.is_destination(&message.destination, link.get_node_id()) {
+            link.report_message_delivered(message.id, message.source_id);
+
+            if let Some(ack_vector) = &mut self.ack_vector {
+                ack_vector.acknowledge(message.id);
+            }
+
+            let size = message.wire_size();
+            let (id, ttl) = (message.id, message.ttl);
+            if !self.keep_delivered || !self.store.insert(link, id, ttl, message.as_data(), size, state) {
+                self.store.mark_seen(id);
+                link.report_message_dropped(id);
+            }
+        } else {
+            let size = message.wire_size();
+            if !self.store.insert(link, message.id, message.ttl, message.as_data(), size, state) {
+                link.report_message_dropped(message.id);
+            }
+        }
+    }
+
+    fn try_ping(&mut self, link: &mut RouterLink, force: bool) {
+        if self.beacon.is_due(link) || force {
+            link.send_packet(LayeredPacket::<S::CopyState>::Ping {
+                source_id: link.get_node_id(),
+                summary_vector: self.store.summary_vector().bits().clone(),
+                ack_vector: self.ack_vector_bits(),
+            });
+
+            self.beacon.mark_sent(link);
+        }
+    }
+
+    /// Whether a request may be sent to `neighbour_id` now, recording it as sent if so.
+    fn take_request_slot(&mut self, link: &RouterLink, neighbour_id: NodeId) -> bool {
+        let request_interval = match self.request_interval {
+            Some(request_interval) => request_interval,
+            None => return true,
+        };
+        let time = link.get_time();
+        let throttled = self.last_request_times
+            .get(&neighbour_id)
+            .is_some_and(|&last_request_time| time < last_request_time + request_interval);
+
+        if !throttled {
+            self.last_request_times.insert(neighbour_id, time);
+        }
+        !throttled
+    }
+
+    fn ack_vector_bits(&self) -> Option<BitVec> {
+        self.ack_vector
+            .as_ref()
+            .map(|ack_vector| ack_vector.bits().clone())
+    }
+
+    fn handle_ack_vector(&mut self, link: &mut RouterLink, other_ack_vector: &Option<BitVec>) {
+        if let (Some(ack_vector), Some(other_ack_vector)) = (&mut self.ack_vector, other_ack_vector) {
+            ack_vector.merge(link, &mut self.store, other_ack_vector);
+        }
+    }
+
+    fn prepare_requested_messages(
+        &mut self,
+        link: &mut RouterLink,
+        summary_vector: &BitVec,
+        source_id: NodeId,
+    ) -> Vec<(Message, S::CopyState)> {
+        let mut messages = vec![];
+        for id in self.store.missing_from(link, source_id, summary_vector) {
+            if self.store.verify_ttl(link, id) {
+                continue;
+            }
+            let stored_message = self.store.get_mut(id).unwrap();
+            let message = Message::from_data(link.get_from_message_buffer(&stored_message.handle).unwrap());
+
+            if let Some(copy_state) = self.strategy.on_forward(&message, &mut stored_message.state, source_id, link.groups()) {
+                link.report_message_sent(message.id, source_id);
+
+                // Anycast messages are consumed by the first member of their group they reach.
+                let consumed = message.destination.is_anycast() && link.is_destination(&message.destination, source_id);
+                if consumed || !self.strategy.retain(&stored_message.state) {
+                    self.store.remove(link, id);
+                    link.report_message_dropped(message.id);
+                }
+
+                messages.push((message, copy_state));
+            }
+        }
+        messages
+    }
+
+    fn handle_messages(
+        &mut self,
+        link: &mut RouterLink,
+        messages: Vec<(Message, S::CopyState)>,
+        source_id: NodeId,
+    ) {
+        if !messages.is_empty() {
+            for (message, state) in messages {
+                link.report_message_received(message.id, source_id);
+                self.insert_message(link, message, state)
+            }
+            if self.ping_on_receive {
+                self.try_ping(link, true);
+            }
+        }
+    }
+}
+
+impl<S: ForwardingStrategy> PacketRouter for LayeredRouter<S> {
+    type Packet = LayeredPacket<S::CopyState>;
+
+    fn on_init(&mut self, link: &mut RouterLink, _id: NodeId) {
+        self.beacon.on_init(link);
+    }
+
+    fn on_message_created(
+        &mut self,
+        link: &mut RouterLink,
+        destination: MessageDestination<NodeId>,
+        payload: Data,
+        ttl: Option<TimeMetric>,
+    ) {
+        let message_id = self.store.next_message_id(link.get_node_id());
+
+        link.report_message_created(message_id, destination.clone(), ttl);
+
+        let message = Message {
+            id: message_id,
+            source_id: link.get_node_id(),
+            destination,
+            payload,
+            ttl,
+        };
+        let state = self.strategy.on_message_created(&message);
+        self.insert_message(link, message, state);
+    }
+
+    fn on_packet_received(&mut self, link: &mut RouterLink, packet: Self::Packet) {
+        match packet {
+            LayeredPacket::Ping {
+                source_id, summary_vector, ack_vector
+            } => {
+                self.handle_ack_vector(link, &ack_vector);
+
+                if summary_vector.any()
+                    && self.store.summary_vector().is_missing_from(&summary_vector)
+                    && self.take_request_slot(link, source_id) {
+                    link.send_packet_to(LayeredPacket::<S::CopyState>::Request {
+                        source_id: link.get_node_id(),
+                        destination_id: source_id,
+                        summary_vector: self.store.summary_vector().bits().clone(),
+                        ack_vector: if self.bilateral_ack {
+                            self.ack_vector_bits()
+                        } else {
+                            None
+                        },
+                        bilateral: self.bilateral_fulfillment,
+                    }, source_id)
+                }
+            }
+            LayeredPacket::Request {
+                source_id,
+                destination_id,
+                summary_vector,
+                ack_vector,
+                bilateral
+            } => {
+                self.handle_ack_vector(link, &ack_vector);
+
+                if destination_id == link.get_node_id() {
+                    let messages = self.prepare_requested_messages(link, &summary_vector, source_id);
+                    let wants_messages = bilateral && self.store.summary_vector().is_missing_from(&summary_vector);
+
+                    if !messages.is_empty() || wants_messages {
+                        link.send_packet_to(LayeredPacket::Fulfillment {
+                            source_id: link.get_node_id(),
+                            destination_id: source_id,
+                            messages,
+                            summary_vector: if wants_messages {
+                                Some(self.store.summary_vector().bits().clone())
+                            } else {
+                                None
+                            },
+                        }, source_id);
+                    }
+                }
+            }
+            LayeredPacket::Fulfillment {
+                source_id, destination_id, messages, summary_vector
+            } => {
+                if destination_id == link.get_node_id() {
+                    self.handle_messages(link, messages, source_id);
+
+                    if let Some(summary_vector) = summary_vector {
+                        let messages = self.prepare_requested_messages(link, &summary_vector, source_id);
+
+                        link.send_packet_to(LayeredPacket::BilateralFulfillment {
+                            source_id: destination_id,
+                            destination_id: source_id,
+                            messages,
+                        }, source_id)
+                    }
+                }
+            }
+            LayeredPacket::BilateralFulfillment { source_id, destination_id, messages } => {
+                if destination_id == link.get_node_id() {
+                    self.handle_messages(link, messages, source_id);
+                }
+            }
+        }
+    }
+
+    fn on_awake(&mut self, link: &mut RouterLink) {
+        self.store.evict_expired(link);
+
+        self.try_ping(link, false);
+    }
+}
@@ -1,8 +1,9 @@
-use crate::utils::{Data, MessageId, NodeId, TimeMetric};
+use serde::{Deserialize, Serialize};
 
 use crate::binary_serde::BinarySerde;
-use serde::{Deserialize, Serialize};
 use crate::message_destination::MessageDestination;
+use crate::utils::{Data, MessageId, NodeId, TimeMetric};
+use crate::wire_size::WireSize;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Message {
@@ -13,4 +14,19 @@ pub struct Message {
     pub ttl: Option<TimeMetric>,
 }
 
+impl Message {
+    pub fn header_size(&self) -> usize {
+        self.id.wire_size()
+            + self.source_id.wire_size()
+            + self.destination.wire_size()
+            + self.ttl.wire_size()
+    }
+}
+
+impl WireSize for Message {
+    fn wire_size(&self) -> usize {
+        self.header_size() + self.payload.wire_size()
+    }
+}
+
 impl BinarySerde for Message {}
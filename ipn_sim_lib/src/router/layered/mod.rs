@@ -0,0 +1,4 @@
+pub mod forwarding_strategy;
+pub mod layered_router;
+pub mod message;
+pub mod packet;
@@ -0,0 +1,97 @@
+use bit_vec::BitVec;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::binary_serde::BinarySerde;
+use crate::packet::{Packet, PacketSize};
+use crate::router::layered::message::Message;
+use crate::utils::NodeId;
+use crate::wire_size::{WireSize, TAG_SIZE};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum LayeredPacket<C> {
+    Ping {
+        source_id: NodeId,
+        summary_vector: BitVec,
+        ack_vector: Option<BitVec>,
+    },
+    /// Asks for every message not in `summary_vector`, the requester's own.
+    Request {
+        source_id: NodeId,
+        destination_id: NodeId,
+        summary_vector: BitVec,
+        ack_vector: Option<BitVec>,
+        bilateral: bool,
+    },
+    Fulfillment {
+        source_id: NodeId,
+        destination_id: NodeId,
+        messages: Vec<(Message, C)>,
+        summary_vector: Option<BitVec>,
+    },
+    BilateralFulfillment {
+        source_id: NodeId,
+        destination_id: NodeId,
+        messages: Vec<(Message, C)>,
+    },
+}
+
+impl<C: Serialize + DeserializeOwned> BinarySerde for LayeredPacket<C> {}
+
+impl<C: Serialize + DeserializeOwned + Clone + WireSize + 'static> Packet for LayeredPacket<C> {
+    const SHARED: bool = true;
+
+    fn packet_type(&self) -> &'static str {
+        match self {
+            LayeredPacket::Ping { .. } => "Ping",
+            LayeredPacket::Request { .. } => "Request",
+            LayeredPacket::Fulfillment { .. } => "Fulfillment",
+            LayeredPacket::BilateralFulfillment { .. } => "BilateralFulfillment",
+        }
+    }
+
+    fn packet_size(&self) -> Option<PacketSize> {
+        Some(match self {
+            LayeredPacket::Ping { source_id, summary_vector, ack_vector } => PacketSize::header(
+                TAG_SIZE + source_id.wire_size() + summary_vector.wire_size() + ack_vector.wire_size()
+            ),
+            LayeredPacket::Request { source_id, destination_id, summary_vector, ack_vector, bilateral } => PacketSize::header(
+                TAG_SIZE
+                    + source_id.wire_size()
+                    + destination_id.wire_size()
+                    + summary_vector.wire_size()
+                    + ack_vector.wire_size()
+                    + bilateral.wire_size()
+            ),
+            LayeredPacket::Fulfillment { source_id, destination_id, messages, summary_vector } => {
+                let messages_size = messages_size(messages);
+                PacketSize::new(
+                    TAG_SIZE
+                        + source_id.wire_size()
+                        + destination_id.wire_size()
+                        + messages_size.header
+                        + summary_vector.wire_size(),
+                    messages_size.payload,
+                )
+            }
+            LayeredPacket::BilateralFulfillment { source_id, destination_id, messages } => {
+                let messages_size = messages_size(messages);
+                PacketSize::new(
+                    TAG_SIZE
+                        + source_id.wire_size()
+                        + destination_id.wire_size()
+                        + messages_size.header,
+                    messages_size.payload,
+                )
+            }
+        })
+    }
+}
+
+fn messages_size<C: WireSize>(messages: &Vec<(Message, C)>) -> PacketSize {
+    let payload = messages
+        .iter()
+        .map(|(message, _)| message.payload.len())
+        .sum::<usize>();
+    PacketSize::new(messages.wire_size() - payload, payload)
+}
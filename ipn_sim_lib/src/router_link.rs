@@ -1,14 +1,25 @@
+use crate::bundle::bundle::{Bundle, BundleId};
+use crate::bundle::custody::{CustodyRefusal, CustodySignal};
+use crate::bundle::status_report::{BundleStatus, StatusReport};
 use crate::events::awake_router_event::AwakeRouterEvent;
 use crate::events::{router_event::{RouterEvent, RouterEventType}, router_event};
 use crate::events::transmit_start_event::TransmitStartEvent;
+use crate::ipn_sim::groups::Groups;
 use crate::ipn_sim::ipn_sim::IpnSim;
+use crate::node::custody_transfers::PendingCustody;
 use crate::node::message_buffer::MessageHandle;
+use crate::node::neighbour_table::Neighbour;
 use crate::node::node::Node;
-use crate::utils::{Data, MessageId, NodeId, TimeMetric};
+use crate::oracle::oracle::Oracle;
+use crate::utils::{Data, MessageId, NodeId, TimeMetric, C};
+use cgmath::MetricSpace;
 use std::cell::RefCell;
 use std::rc::Rc;
 use crate::utils::Shared;
-use crate::message_destination::MessageDestination;
+use crate::message_destination::{IsIncluded, MessageDestination};
+use crate::ltp::engine::round_trip_time;
+use crate::ltp::segment::Segment;
+use crate::packet::{Packet, PacketData};
 
 
 pub struct RouterLink<'a> {
@@ -34,6 +45,10 @@ impl<'a> RouterLink<'a> {
         self.node.message_buffer.add_message(data)
     }
 
+    pub fn add_sized_to_message_buffer(&mut self, data: Data, size: usize) -> Option<MessageHandle> {
+        self.node.message_buffer.add_sized_message(data, size)
+    }
+
     pub fn get_from_message_buffer(&self, message_handle: &MessageHandle) -> Option<&Data> {
         self.node.message_buffer.get_message(message_handle)
     }
@@ -49,13 +64,39 @@ impl<'a> RouterLink<'a> {
     }
 
     pub fn add_to_transmit_buffer(&mut self, data: Data) {
-        let transmit_start = self.node.transceiver.add_to_buffer(data, self.sim.time);
-        self.sim.add_event(
-            transmit_start,
-            TransmitStartEvent {
-                node: Rc::clone(&self.node_rc),
-            },
-        );
+        self.transmit(PacketData::raw(data));
+    }
+
+    pub fn send_packet<P: Packet>(&mut self, packet: P) {
+        self.transmit(PacketData::from_packet(packet));
+    }
+
+    /// Sends a packet to a single node. Over LTP its red part is delivered reliably, otherwise it
+    /// is broadcast like any other packet.
+    pub fn send_packet_to<P: Packet>(&mut self, packet: P, destination_id: NodeId) {
+        self.transmit_to(PacketData::from_packet(packet), Some(destination_id));
+    }
+
+    fn transmit(&mut self, packet: PacketData) {
+        self.transmit_to(packet, None);
+    }
+
+    fn transmit_to(&mut self, packet: PacketData, destination_id: Option<NodeId>) {
+        let destination_id = destination_id.filter(|&destination_id| destination_id != self.node.id);
+        if self.node.ltp_engine.is_some() {
+            let round_trip_time = destination_id.map_or(0, |destination_id| {
+                round_trip_time(self.node.position, self.sim.get_node(destination_id).borrow().position)
+            });
+            let max_segment_size = self.node.transceiver.max_frame_size
+                .saturating_sub(self.node.transceiver.frame_header_size + Segment::max_data_header_size());
+            let actions = self.node.ltp_engine
+                .as_mut()
+                .unwrap()
+                .send_block(self.node.id, destination_id, packet, max_segment_size, round_trip_time);
+            actions.dispatch(self.node, &self.node_rc, self.sim);
+        } else {
+            TransmitStartEvent::schedule(self.node, &self.node_rc, self.sim, packet);
+        }
     }
 
     pub fn sleep_for(&mut self, sleep_time: TimeMetric) {
@@ -71,6 +112,49 @@ impl<'a> RouterLink<'a> {
         self.sim.time
     }
 
+    /// Nodes currently able to transceive with this node, once discovered. Empty before the
+    /// simulation's nodes are installed, e.g. in `on_init`.
+    pub fn neighbours(&mut self) -> Vec<Neighbour> {
+        let nodes = match self.sim.nodes.as_ref() {
+            Some(nodes) => nodes,
+            None => return vec![],
+        };
+        let in_range = nodes
+            .iter()
+            .filter(|other_node| !Rc::ptr_eq(other_node, &self.node_rc))
+            .filter_map(|other_node| {
+                let other_node = other_node.borrow();
+                self.sim.nodes_can_transceive(self.node, &other_node).then(|| {
+                    let distance = self.node.position.distance(other_node.position);
+                    Neighbour {
+                        node_id: other_node.id,
+                        distance,
+                        light_time: (distance / C) as TimeMetric,
+                    }
+                })
+            })
+            .collect();
+        self.node.neighbour_table.update(in_range, self.sim.time)
+    }
+
+    /// Omniscient access to the simulation, if the node was granted it.
+    pub fn oracle(&mut self) -> Option<Oracle<'_>> {
+        if self.node.oracle_access {
+            Some(Oracle::new(self.node, &self.node_rc, self.sim))
+        } else {
+            None
+        }
+    }
+
+    /// Group membership, which the scenario makes known to every node.
+    pub fn groups(&self) -> &Groups {
+        &self.sim.groups
+    }
+
+    pub fn is_destination(&self, destination: &MessageDestination<NodeId>, node_id: NodeId) -> bool {
+        destination.is_included(&node_id, &self.sim.groups)
+    }
+
     pub fn get_single_message_destination(&self, node_id: NodeId) -> MessageDestination<Shared<Node>> {
         MessageDestination::<Shared<Node>>::Single(self.sim.get_node(node_id))
     }
@@ -104,6 +188,8 @@ impl<'a> RouterLink<'a> {
                 MessageDestination::Multiple(ids) => {
                     self.get_multiple_message_destination(&ids)
                 }
+                MessageDestination::Multicast(group_id) => MessageDestination::<Shared<Node>>::Multicast(group_id),
+                MessageDestination::Anycast(group_id) => MessageDestination::<Shared<Node>>::Anycast(group_id),
             },
             ttl,
         });
@@ -124,6 +210,10 @@ impl<'a> RouterLink<'a> {
     }
 
     pub fn report_message_delivered(&mut self, id: MessageId, source_node_id: NodeId) {
+        self.sim.delivered_messages
+            .entry(id)
+            .or_default()
+            .push(self.node.id);
         self.report(RouterEventType::MessageDelivered {
             id,
             source_node: self.sim.get_node(source_node_id),
@@ -135,4 +225,73 @@ impl<'a> RouterLink<'a> {
             id,
         })
     }
+
+    pub fn report_message_suppressed(&mut self, id: MessageId, neighbour_node_id: NodeId) {
+        self.report(RouterEventType::MessageSuppressed {
+            id,
+            neighbour_node: self.sim.get_node(neighbour_node_id),
+        })
+    }
+
+    /// Takes custody of a bundle handed to `to_id`. It must stay in the message buffer under
+    /// `handle` until a custody signal releases it, and the router is woken when the
+    /// retransmission timer runs out.
+    pub fn request_custody(&mut self, bundle: &Bundle, handle: MessageHandle, to_id: NodeId) {
+        self.node.custody_transfers.request(bundle.id(), handle, to_id, self.sim.time);
+        self.sleep_for(self.node.custody_transfers.retransmission_timeout);
+    }
+
+    pub fn custody_signal(&self, bundle: &Bundle, refusal: Option<CustodyRefusal>) -> CustodySignal {
+        CustodySignal {
+            bundle_id: bundle.id(),
+            node_id: self.node.id,
+            refusal,
+        }
+    }
+
+    /// Resolves a custody transfer. Released bundles are removed from the message buffer, refused
+    /// ones are returned so the router can try elsewhere.
+    pub fn on_custody_signal(&mut self, signal: &CustodySignal) -> Option<PendingCustody> {
+        if !signal.releases_custody() && self.node.custody_transfers
+            .get(&signal.bundle_id)
+            .is_none_or(|pending_custody| pending_custody.to_id != signal.node_id) {
+            return None;
+        }
+        let pending_custody = self.node.custody_transfers.resolve(&signal.bundle_id)?;
+        if signal.releases_custody() {
+            self.remove_from_message_buffer(&pending_custody.handle);
+            self.report_message_dropped(signal.bundle_id.message_id());
+            None
+        } else {
+            Some(pending_custody)
+        }
+    }
+
+    /// Stops awaiting a custody signal, e.g. once retransmissions have been exhausted. The bundle is
+    /// left in the message buffer.
+    pub fn cancel_custody(&mut self, bundle_id: &BundleId) -> Option<PendingCustody> {
+        self.node.custody_transfers.resolve(bundle_id)
+    }
+
+    /// Custody transfers whose retransmission timer has run out.
+    pub fn due_custody_transfers(&self) -> Vec<PendingCustody> {
+        self.node.custody_transfers.due(self.sim.time)
+    }
+
+    /// Reports a bundle status as the equivalent router event.
+    pub fn report_bundle_status(&mut self, bundle: &Bundle, status: BundleStatus) -> StatusReport {
+        let id = bundle.message_id();
+        match status {
+            BundleStatus::Received { from_id } => self.report_message_received(id, from_id),
+            BundleStatus::Forwarded { to_id } => self.report_message_sent(id, to_id),
+            BundleStatus::Delivered => self.report_message_delivered(id, bundle.source_id().unwrap_or(self.node.id)),
+            BundleStatus::Deleted(_) => self.report_message_dropped(id),
+        }
+        StatusReport {
+            bundle_id: bundle.id(),
+            node_id: self.node.id,
+            status,
+            time: self.sim.time,
+        }
+    }
 }
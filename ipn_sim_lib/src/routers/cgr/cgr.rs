@@ -0,0 +1,191 @@
+use std::mem::take;
+
+use crate::binary_serde::BinarySerde;
+use crate::message_destination::MessageDestination;
+use crate::node::message_buffer::MessageHandle;
+use crate::router::layered::message::Message;
+use crate::router::PacketRouter;
+use crate::router_link::RouterLink;
+use crate::routers::cgr::contact_plan::ContactPlan;
+use crate::routers::cgr::packet::Packet;
+use crate::utils;
+use crate::utils::{Data, NodeId, TimeMetric};
+use crate::wire_size::WireSize;
+
+/// How often a bundle waiting on a started contact checks whether its next hop is in range.
+const CONTACT_POLL_INTERVAL: TimeMetric = 1_000_000_000 * 60;
+
+/// Contact Graph Routing: bundles are forwarded unicast along the earliest arrival route through
+/// the node's contact plan. Bundles whose contact ends without the next hop being discovered are
+/// re-routed around it.
+#[derive(Clone)]
+pub struct Cgr {
+    contact_plan: ContactPlan,
+    queued_bundles: Vec<QueuedBundle>,
+    next_message_index: u16,
+}
+
+#[derive(Clone)]
+struct QueuedBundle {
+    handle: MessageHandle,
+    target_id: NodeId,
+    size: usize,
+    contact: usize,
+    excluded_contacts: Vec<usize>,
+}
+
+impl Cgr {
+    pub fn new(contact_plan: ContactPlan) -> Self {
+        Self {
+            contact_plan,
+            queued_bundles: vec![],
+            next_message_index: 0,
+        }
+    }
+
+    pub fn contact_plan(&self) -> &ContactPlan {
+        &self.contact_plan
+    }
+
+    /// Books the first contact of the best route to `target_id` and queues the bundle on it.
+    fn route(&mut self, link: &mut RouterLink, message: &Message, target_id: NodeId, excluded_contacts: Vec<usize>) {
+        let time = link.get_time();
+        let size = message.wire_size();
+
+        let route = self.contact_plan
+            .find_route(link.get_node_id(), target_id, size, time, &excluded_contacts)
+            .filter(|route| message.ttl.is_none_or(|ttl| route.arrival_time <= ttl));
+
+        if let Some(route) = route {
+            if let Some(handle) = link.add_sized_to_message_buffer(message.as_data(), size) {
+                self.contact_plan.book(route.first_contact, size);
+                self.queued_bundles.push(QueuedBundle {
+                    handle,
+                    target_id,
+                    size,
+                    contact: route.first_contact,
+                    excluded_contacts,
+                });
+                link.sleep_for(route.departure_time - time);
+                return;
+            }
+        }
+        link.report_message_dropped(message.id);
+    }
+
+    /// The anycast target with the earliest arrival route.
+    fn nearest_target(&self, link: &RouterLink, target_ids: Vec<NodeId>, size: usize) -> Option<NodeId> {
+        let node_id = link.get_node_id();
+        target_ids
+            .into_iter()
+            .filter(|&target_id| target_id != node_id)
+            .filter_map(|target_id| {
+                self.contact_plan
+                    .find_route(node_id, target_id, size, link.get_time(), &[])
+                    .map(|route| (route.arrival_time, target_id))
+            })
+            .min()
+            .map(|(_, target_id)| target_id)
+    }
+}
+
+impl PacketRouter for Cgr {
+    type Packet = Packet;
+
+    fn on_message_created(
+        &mut self,
+        link: &mut RouterLink,
+        destination: MessageDestination<NodeId>,
+        payload: Data,
+        ttl: Option<TimeMetric>,
+    ) {
+        let node_id = link.get_node_id();
+        let message_id = utils::next_message_id(node_id, &mut self.next_message_index);
+
+        link.report_message_created(message_id, destination.clone(), ttl);
+
+        let target_ids = destination
+            .node_ids(link.groups())
+            .unwrap_or_else(|| self.contact_plan.node_ids().into_iter().collect());
+
+        let message = Message {
+            id: message_id,
+            source_id: node_id,
+            destination,
+            payload,
+            ttl,
+        };
+
+        let target_ids = if message.destination.is_anycast() {
+            self.nearest_target(link, target_ids, message.wire_size()).into_iter().collect()
+        } else {
+            target_ids
+        };
+
+        for target_id in target_ids {
+            if target_id != node_id {
+                self.route(link, &message, target_id, vec![]);
+            }
+        }
+    }
+
+    fn on_packet_received(&mut self, link: &mut RouterLink, packet: Packet) {
+        if packet.destination_id != link.get_node_id() {
+            return;
+        }
+        link.report_message_received(packet.message.id, packet.source_id);
+
+        if packet.target_id == link.get_node_id() && link.is_destination(&packet.message.destination, link.get_node_id()) {
+            link.report_message_delivered(packet.message.id, packet.message.source_id);
+            link.report_message_dropped(packet.message.id);
+        } else {
+            self.route(link, &packet.message, packet.target_id, vec![]);
+        }
+    }
+
+    fn on_awake(&mut self, link: &mut RouterLink) {
+        let time = link.get_time();
+        let neighbour_ids = link
+            .neighbours()
+            .into_iter()
+            .map(|neighbour| neighbour.node_id)
+            .collect::<Vec<_>>();
+
+        for mut queued_bundle in take(&mut self.queued_bundles) {
+            let contact = self.contact_plan.contacts[queued_bundle.contact].clone();
+            if time < contact.start {
+                self.queued_bundles.push(queued_bundle);
+                continue;
+            }
+
+            let message = Message::from_data(link.get_from_message_buffer(&queued_bundle.handle).unwrap());
+            let expired = message.ttl.is_some_and(|ttl| ttl <= time);
+            let in_range = neighbour_ids.contains(&contact.to_id);
+
+            if !expired && !in_range && time < contact.end {
+                link.sleep_for(CONTACT_POLL_INTERVAL.min(contact.end - time));
+                self.queued_bundles.push(queued_bundle);
+                continue;
+            }
+
+            link.remove_from_message_buffer(&queued_bundle.handle);
+
+            if expired {
+                link.report_message_dropped(message.id);
+            } else if in_range {
+                link.report_message_sent(message.id, contact.to_id);
+                link.report_message_dropped(message.id);
+                link.send_packet_to(Packet {
+                    source_id: link.get_node_id(),
+                    destination_id: contact.to_id,
+                    target_id: queued_bundle.target_id,
+                    message,
+                }, contact.to_id);
+            } else {
+                self.contact_plan.release(queued_bundle.contact, queued_bundle.size);
+                queued_bundle.excluded_contacts.push(queued_bundle.contact);
+                self.route(link, &message, queued_bundle.target_id, queued_bundle.excluded_contacts);
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use rustc_hash::FxHashSet;
+
+use crate::utils::{NodeId, TimeMetric};
+
+/// A scheduled window during which `from_id` can transmit to `to_id`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlannedContact {
+    pub from_id: NodeId,
+    pub to_id: NodeId,
+    pub start: TimeMetric,
+    pub end: TimeMetric,
+    /// Bytes per ns.
+    pub rate: f64,
+    /// One way light time.
+    pub range: TimeMetric,
+}
+
+impl PlannedContact {
+    pub fn capacity(&self) -> f64 {
+        (self.end - self.start) as f64 * self.rate
+    }
+}
+
+/// The first contact of a route through the contact graph and when the bundle would arrive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContactRoute {
+    pub first_contact: usize,
+    pub departure_time: TimeMetric,
+    pub arrival_time: TimeMetric,
+}
+
+/// State of a route search through the contact graph.
+struct RouteSearch<'a> {
+    size: usize,
+    excluded: &'a [usize],
+    /// Earliest arrival over each contact, with the first contact and departure time taken.
+    labels: Vec<(TimeMetric, usize, TimeMetric)>,
+    queue: BinaryHeap<Reverse<(TimeMetric, usize)>>,
+}
+
+/// The contacts a node expects to take place, along with the capacity of each not yet booked.
+#[derive(Clone)]
+pub struct ContactPlan {
+    pub contacts: Vec<PlannedContact>,
+    residual_capacities: Vec<f64>,
+}
+
+impl ContactPlan {
+    pub fn new(contacts: Vec<PlannedContact>) -> Self {
+        Self {
+            residual_capacities: contacts.iter().map(PlannedContact::capacity).collect(),
+            contacts,
+        }
+    }
+
+    pub fn residual_capacity(&self, contact: usize) -> f64 {
+        self.residual_capacities[contact]
+    }
+
+    pub fn book(&mut self, contact: usize, size: usize) {
+        self.residual_capacities[contact] -= size as f64;
+    }
+
+    pub fn release(&mut self, contact: usize, size: usize) {
+        self.residual_capacities[contact] += size as f64;
+    }
+
+    pub fn node_ids(&self) -> FxHashSet<NodeId> {
+        self.contacts
+            .iter()
+            .flat_map(|contact| [contact.from_id, contact.to_id])
+            .collect()
+    }
+
+    /// Dijkstra over the contact graph, where contacts are vertices and a bundle of `size` bytes
+    /// moves from one contact to the next once it has been transmitted and has crossed the range.
+    /// Contacts without a positive rate or enough residual capacity, or listed in `excluded`, are
+    /// skipped.
+    pub fn find_route(
+        &self,
+        source_id: NodeId,
+        destination_id: NodeId,
+        size: usize,
+        start_time: TimeMetric,
+        excluded: &[usize],
+    ) -> Option<ContactRoute> {
+        let mut search = RouteSearch {
+            size,
+            excluded,
+            labels: vec![(TimeMetric::MAX, 0, 0); self.contacts.len()],
+            queue: BinaryHeap::new(),
+        };
+
+        self.relax(&mut search, source_id, start_time, None);
+
+        while let Some(Reverse((arrival_time, index))) = search.queue.pop() {
+            if arrival_time > search.labels[index].0 {
+                continue;
+            }
+            let contact = &self.contacts[index];
+            if contact.to_id == destination_id {
+                let (_, first_contact, departure_time) = search.labels[index];
+                return Some(ContactRoute {
+                    first_contact,
+                    departure_time,
+                    arrival_time,
+                });
+            }
+            if contact.to_id != source_id {
+                self.relax(&mut search, contact.to_id, arrival_time, Some(index));
+            }
+        }
+
+        None
+    }
+
+    /// Updates the arrival times of the contacts leaving `node_id` for a bundle available at `time`.
+    fn relax(&self, search: &mut RouteSearch, node_id: NodeId, time: TimeMetric, via_contact: Option<usize>) {
+        let size = search.size;
+        for (index, contact) in self.contacts.iter().enumerate() {
+            if contact.from_id != node_id || contact.rate <= 0. || search.excluded.contains(&index) {
+                continue;
+            }
+            let departure_time = time.max(contact.start);
+            let transmit_end = departure_time.saturating_add((size as f64 / contact.rate) as TimeMetric);
+            if transmit_end > contact.end || self.residual_capacities[index] < size as f64 {
+                continue;
+            }
+            let arrival_time = transmit_end + contact.range;
+            if arrival_time < search.labels[index].0 {
+                let (first_contact, first_departure_time) = via_contact.map_or(
+                    (index, departure_time),
+                    |via_contact| (search.labels[via_contact].1, search.labels[via_contact].2),
+                );
+                search.labels[index] = (arrival_time, first_contact, first_departure_time);
+                search.queue.push(Reverse((arrival_time, index)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routers::cgr::contact_plan::{ContactPlan, ContactRoute, PlannedContact};
+
+    fn contact(from_id: u16, to_id: u16, start: u64, end: u64) -> PlannedContact {
+        PlannedContact {
+            from_id,
+            to_id,
+            start,
+            end,
+            rate: 1.,
+            range: 5,
+        }
+    }
+
+    #[test]
+    fn test_find_route() {
+        let mut contact_plan = ContactPlan::new(vec![
+            contact(0, 1, 0, 100),
+            contact(1, 2, 50, 100),
+            contact(0, 2, 200, 300),
+        ]);
+
+        assert_eq!(contact_plan.find_route(0, 2, 10, 0, &[]), Some(ContactRoute {
+            first_contact: 0,
+            departure_time: 0,
+            arrival_time: 65,
+        }));
+
+        assert_eq!(contact_plan.find_route(0, 2, 10, 0, &[0]).unwrap().first_contact, 2);
+
+        contact_plan.book(1, 45);
+        assert_eq!(contact_plan.find_route(0, 2, 10, 0, &[]).unwrap().first_contact, 2);
+
+        let mut idle_contact = contact(0, 2, 0, 100);
+        idle_contact.rate = 0.;
+        assert_eq!(ContactPlan::new(vec![idle_contact]).find_route(0, 2, 10, 0, &[]), None);
+    }
+}
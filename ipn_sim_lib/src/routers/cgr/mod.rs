@@ -0,0 +1,3 @@
+pub mod cgr;
+pub mod contact_plan;
+pub mod packet;
@@ -0,0 +1,233 @@
+use std::mem::take;
+
+use rustc_hash::FxHashMap;
+
+use crate::binary_serde::BinarySerde;
+use crate::bundle::bundle::{Bundle, BundleId};
+use crate::bundle::custody::CustodyRefusal;
+use crate::bundle::status_report::{BundleStatus, DeletionReason};
+use crate::message_destination::MessageDestination;
+use crate::node::message_buffer::MessageHandle;
+use crate::router::PacketRouter;
+use crate::router_link::RouterLink;
+use crate::routers::custody_relay::packet::Packet;
+use crate::utils::{Data, NodeId, TimeMetric};
+use crate::wire_size::WireSize;
+
+/// Reliable single copy reference router. Each bundle is handed to a discovered neighbour which is
+/// its destination, or else to the first one it has not yet been refused by, under custody
+/// transfer: the sender keeps the bundle, retransmitting it until the neighbour accepts custody
+/// or `max_attempts` is reached.
+#[derive(Clone)]
+pub struct CustodyRelay {
+    poll_interval: TimeMetric,
+    max_attempts: u8,
+    default_lifetime: TimeMetric,
+    next_sequence_number: u16,
+    held_bundles: Vec<HeldBundle>,
+    /// Bundles created or received, until they expire.
+    received_bundle_ids: FxHashMap<BundleId, TimeMetric>,
+    next_poll_time: Option<TimeMetric>,
+}
+
+/// A bundle in the custody of this node that is not currently being transferred.
+#[derive(Clone)]
+struct HeldBundle {
+    handle: MessageHandle,
+    excluded_node_ids: Vec<NodeId>,
+}
+
+impl CustodyRelay {
+    pub fn new(poll_interval: TimeMetric, max_attempts: u8, default_lifetime: TimeMetric) -> Self {
+        Self {
+            poll_interval,
+            max_attempts,
+            default_lifetime,
+            next_sequence_number: 0,
+            held_bundles: vec![],
+            received_bundle_ids: FxHashMap::default(),
+            next_poll_time: None,
+        }
+    }
+
+    /// Wakes the router after `delay` unless it is already due to wake by then.
+    fn poll_in(&mut self, link: &mut RouterLink, delay: TimeMetric) {
+        let poll_time = link.get_time() + delay;
+        if self.next_poll_time.is_none_or(|next_poll_time| next_poll_time > poll_time) {
+            self.next_poll_time = Some(poll_time);
+            link.sleep_for(delay);
+        }
+    }
+
+    fn hold(&mut self, link: &mut RouterLink, bundle: &Bundle, excluded_node_ids: Vec<NodeId>) -> bool {
+        match link.add_sized_to_message_buffer(bundle.as_data(), bundle.wire_size()) {
+            Some(handle) => {
+                self.held_bundles.push(HeldBundle {
+                    handle,
+                    excluded_node_ids,
+                });
+                true
+            }
+            None => {
+                link.report_bundle_status(bundle, BundleStatus::Deleted(DeletionReason::DepletedStorage));
+                false
+            }
+        }
+    }
+
+    fn transfer(&mut self, link: &mut RouterLink, bundle: &Bundle, handle: MessageHandle, to_id: NodeId) -> bool {
+        let mut bundle = bundle.clone();
+        if !bundle.prepare_forward(link.get_node_id()) {
+            link.remove_from_message_buffer(&handle);
+            link.report_bundle_status(&bundle, BundleStatus::Deleted(DeletionReason::HopLimitExceeded));
+            return false;
+        }
+        bundle.primary.custody_transfer_requested = true;
+        bundle.set_custodian(link.get_node_id());
+
+        link.report_bundle_status(&bundle, BundleStatus::Forwarded { to_id });
+        link.request_custody(&bundle, handle, to_id);
+        link.send_packet_to(Packet::Bundle {
+            source_id: link.get_node_id(),
+            destination_id: to_id,
+            bundle,
+        }, to_id);
+        true
+    }
+
+    fn retransmit_due(&mut self, link: &mut RouterLink, neighbour_ids: &[NodeId]) {
+        for pending_custody in link.due_custody_transfers() {
+            let bundle = Bundle::from_data(link.get_from_message_buffer(&pending_custody.handle).unwrap());
+
+            if pending_custody.attempts < self.max_attempts && neighbour_ids.contains(&pending_custody.to_id) {
+                self.transfer(link, &bundle, pending_custody.handle, pending_custody.to_id);
+            } else {
+                link.cancel_custody(&pending_custody.bundle_id);
+                self.held_bundles.push(HeldBundle {
+                    handle: pending_custody.handle,
+                    excluded_node_ids: vec![pending_custody.to_id],
+                });
+            }
+        }
+    }
+}
+
+impl PacketRouter for CustodyRelay {
+    type Packet = Packet;
+
+    fn on_message_created(
+        &mut self,
+        link: &mut RouterLink,
+        destination: MessageDestination<NodeId>,
+        payload: Data,
+        ttl: Option<TimeMetric>,
+    ) {
+        let time = link.get_time();
+        let bundle = Bundle::new(
+            link.get_node_id(),
+            self.next_sequence_number,
+            destination.clone(),
+            payload,
+            time,
+            ttl.map_or(self.default_lifetime, |ttl| ttl.saturating_sub(time)),
+        );
+        self.next_sequence_number = self.next_sequence_number.wrapping_add(1);
+
+        link.report_message_created(bundle.message_id(), destination, ttl);
+        self.received_bundle_ids.insert(bundle.id(), bundle.expiry_time());
+
+        if self.hold(link, &bundle, vec![]) {
+            self.poll_in(link, 0);
+        }
+    }
+
+    fn on_packet_received(&mut self, link: &mut RouterLink, packet: Packet) {
+        match packet {
+            Packet::Bundle { source_id, destination_id, bundle } => {
+                if destination_id != link.get_node_id() {
+                    return;
+                }
+                link.report_bundle_status(&bundle, BundleStatus::Received { from_id: source_id });
+
+                let refusal = if self.received_bundle_ids.insert(bundle.id(), bundle.expiry_time()).is_some() {
+                    link.report_bundle_status(&bundle, BundleStatus::Deleted(DeletionReason::TransmissionCancelled));
+                    Some(CustodyRefusal::Redundant)
+                } else if bundle.is_destined_to(link.get_node_id(), link.groups()) {
+                    link.report_bundle_status(&bundle, BundleStatus::Delivered);
+                    link.report_bundle_status(&bundle, BundleStatus::Deleted(DeletionReason::TransmissionCancelled));
+                    None
+                } else if self.hold(link, &bundle, vec![source_id]) {
+                    self.poll_in(link, 0);
+                    None
+                } else {
+                    self.received_bundle_ids.remove(&bundle.id());
+                    Some(CustodyRefusal::DepletedStorage)
+                };
+
+                if bundle.primary.custody_transfer_requested {
+                    link.send_packet_to(Packet::Signal {
+                        destination_id: source_id,
+                        signal: link.custody_signal(&bundle, refusal),
+                    }, source_id);
+                }
+            }
+            Packet::Signal { destination_id, signal } => {
+                if destination_id != link.get_node_id() {
+                    return;
+                }
+                if let Some(pending_custody) = link.on_custody_signal(&signal) {
+                    self.held_bundles.push(HeldBundle {
+                        handle: pending_custody.handle,
+                        excluded_node_ids: vec![pending_custody.to_id],
+                    });
+                    self.poll_in(link, 0);
+                }
+            }
+        }
+    }
+
+    fn on_awake(&mut self, link: &mut RouterLink) {
+        if self.next_poll_time.is_some_and(|next_poll_time| next_poll_time <= link.get_time()) {
+            self.next_poll_time = None;
+        }
+
+        let time = link.get_time();
+        self.received_bundle_ids.retain(|_, expiry_time| *expiry_time > time);
+
+        let neighbour_ids = link
+            .neighbours()
+            .into_iter()
+            .map(|neighbour| neighbour.node_id)
+            .collect::<Vec<_>>();
+
+        self.retransmit_due(link, &neighbour_ids);
+
+        for held_bundle in take(&mut self.held_bundles) {
+            let bundle = Bundle::from_data(link.get_from_message_buffer(&held_bundle.handle).unwrap());
+
+            if bundle.is_expired(link.get_time()) {
+                link.remove_from_message_buffer(&held_bundle.handle);
+                link.report_bundle_status(&bundle, BundleStatus::Deleted(DeletionReason::LifetimeExpired));
+                continue;
+            }
+
+            let to_id = neighbour_ids
+                .iter()
+                .find(|&&node_id| bundle.is_destined_to(node_id, link.groups()))
+                .or_else(|| neighbour_ids
+                    .iter()
+                    .find(|node_id| !held_bundle.excluded_node_ids.contains(node_id)));
+
+            match to_id {
+                Some(&to_id) => {
+                    self.transfer(link, &bundle, held_bundle.handle, to_id);
+                }
+                None => self.held_bundles.push(held_bundle),
+            }
+        }
+
+        if !self.held_bundles.is_empty() {
+            self.poll_in(link, self.poll_interval);
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod custody_relay;
+pub mod packet;
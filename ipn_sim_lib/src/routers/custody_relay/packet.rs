@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::binary_serde::BinarySerde;
+use crate::bundle::bundle::Bundle;
+use crate::bundle::custody::CustodySignal;
+use crate::packet::{Packet as PacketTrait, PacketSize};
+use crate::utils::NodeId;
+use crate::wire_size::{WireSize, TAG_SIZE};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum Packet {
+    Bundle {
+        source_id: NodeId,
+        destination_id: NodeId,
+        bundle: Bundle,
+    },
+    Signal {
+        destination_id: NodeId,
+        signal: CustodySignal,
+    },
+}
+
+impl BinarySerde for Packet {}
+
+impl PacketTrait for Packet {
+    fn packet_type(&self) -> &'static str {
+        match self {
+            Packet::Bundle { .. } => "Bundle",
+            Packet::Signal { .. } => "CustodySignal",
+        }
+    }
+
+    fn packet_size(&self) -> Option<PacketSize> {
+        Some(match self {
+            Packet::Bundle { source_id, destination_id, bundle } => PacketSize::new(
+                TAG_SIZE + source_id.wire_size() + destination_id.wire_size() + bundle.header_size(),
+                bundle.payload.len(),
+            ),
+            Packet::Signal { destination_id, signal } => PacketSize::header(
+                TAG_SIZE + destination_id.wire_size() + signal.wire_size()
+            ),
+        })
+    }
+}
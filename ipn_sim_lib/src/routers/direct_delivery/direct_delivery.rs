@@ -0,0 +1,89 @@
+use crate::message_destination::{IsIncluded, MessageDestination};
+use crate::ipn_sim::groups::Groups;
+use crate::router::components::ack_vector::Ack;
+use crate::router::components::bloom_filter::BloomParams;
+use crate::router::layered::forwarding_strategy::ForwardingStrategy;
+use crate::router::layered::layered_router::{LayeredRouter, Layers};
+use crate::router::layered::message::Message;
+use crate::utils::{NodeId, TimeMetric};
+
+/// Lower bound baseline: the source holds every message until it meets a destination, dropping
+/// it once handed to the last one.
+pub type DirectDelivery = LayeredRouter<DestinationOnly>;
+
+#[derive(Clone)]
+pub struct DestinationOnly;
+
+impl ForwardingStrategy for DestinationOnly {
+    /// Whether the message has been handed to its only destination.
+    type CopyState = bool;
+
+    fn on_message_created(&mut self, _message: &Message) -> bool {
+        false
+    }
+
+    fn on_forward(&mut self, message: &Message, delivered: &mut bool, neighbour_id: NodeId, groups: &Groups) -> Option<bool> {
+        if message.destination.is_included(&neighbour_id, groups) {
+            *delivered = matches!(message.destination, MessageDestination::Single(_));
+            Some(*delivered)
+        } else {
+            None
+        }
+    }
+
+    fn retain(&self, delivered: &bool) -> bool {
+        !*delivered
+    }
+}
+
+impl LayeredRouter<DestinationOnly> {
+    pub fn new(summary_vector: impl Into<BloomParams>, beacon_interval: TimeMetric) -> Self {
+        Self::with_strategy(DestinationOnly, Layers {
+            summary_vector: summary_vector.into(),
+            beacon_interval,
+            ping_on_receive: false,
+            ack: Ack::None,
+            bilateral_fulfillment: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Point3;
+
+    use crate::events::create_message_event::CreateMessageEvent;
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::message_destination::MessageDestination;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::node::node_builder::NodeBuilder;
+    use crate::routers::direct_delivery::direct_delivery::DirectDelivery;
+    use crate::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
+
+    #[test]
+    fn test_source_drops_delivered_message() {
+        let hour = 1_000_000_000 * 3600;
+        let mut builder = IpnSimBuilder::new(hour);
+        for &x in &[0., 1.] {
+            builder = builder.add_node(NodeBuilder::new()
+                .name("node")
+                .movement(StaticMovement::new(Point3::new(x, 0., 0.)))
+                .message_buffer_size(1024)
+                .router(DirectDelivery::new(64, hour / 4))
+                .transceive_speed(1.)
+                .transceive_guard(SimpleTransceiveGuard::new(10.)));
+        }
+        let source = builder.get_node(0);
+        let mut sim = builder
+            .add_event(0, CreateMessageEvent {
+                node: source,
+                destination: MessageDestination::Single(1),
+                payload: vec![0; 8].into_boxed_slice(),
+                ttl: None,
+            })
+            .build();
+        sim.run();
+
+        assert_eq!(sim.get_node(0).borrow().message_buffer.get_occupancy(), 0.);
+    }
+}
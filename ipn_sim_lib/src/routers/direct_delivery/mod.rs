@@ -0,0 +1 @@
+pub mod direct_delivery;
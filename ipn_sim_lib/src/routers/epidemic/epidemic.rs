@@ -1,248 +1,132 @@
-use std::borrow::Borrow;
-use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
-use std::hash::Hasher;
-
-use bit_vec::{BitBlock, BitVec};
-use rand::random;
-
-use crate::binary_serde::BinarySerde;
-use crate::node::message_buffer::MessageHandle;
-use crate::router::Router;
-use crate::router_link::RouterLink;
+use std::marker::PhantomData;
+
+use crate::ipn_sim::groups::Groups;
+use crate::router::components::bloom_filter::BloomParams;
+use crate::router::layered::forwarding_strategy::ForwardingStrategy;
+use crate::router::layered::layered_router::{LayeredRouter, Layers};
+use crate::router::layered::message::Message;
 use crate::routers::epidemic::flavour::Flavour;
-use crate::routers::epidemic::message::Message;
-use crate::routers::epidemic::packet::Packet;
-use crate::utils::{Data, MessageId, NodeId, TimeMetric};
-
-use rustc_hash::{FxHashMap, FxHasher};
-use crate::message_destination::{MessageDestination, IsIncluded};
-
-#[derive(Clone)]
-pub struct Epidemic<F: Flavour> {
-    pub node_id: Option<NodeId>,
-    pub summary_vector: BitVec,
-    pub message_table: FxHashMap<usize, MessageHandle>,
-    last_ping: FxHashMap<NodeId, TimeMetric>,
-    reconnect_time: TimeMetric,
-    next_message_index: u16,
-    flavour: Option<F>,
-}
+use crate::utils::{NodeId, TimeMetric};
 
-impl<F: Flavour> Epidemic<F> {
-    pub fn new(summary_vector_size: usize, reconnect_time: TimeMetric) -> Self {
-        Self {
-            node_id: None,
-            summary_vector: BitVec::from_elem(summary_vector_size, false),
-            message_table: FxHashMap::default(),
-            last_ping: FxHashMap::default(),
-            reconnect_time,
-            next_message_index: 0,
-            flavour: Some(F::new(summary_vector_size)),
-        }
-    }
+/// How often an [`Epidemic`] router pings its neighbours.
+const PING_INTERVAL: TimeMetric = 1_000_000_000 * 60 * 15;
 
-    fn ping(&mut self, link: &mut RouterLink) {
-        link.add_to_transmit_buffer(
-            Packet::<F::PingPacket, F::RequestPacket, F::FulfillmentPacket>::Ping(
-                self.flavour.as_ref().unwrap().make_ping_packet(self),
-            )
-                .as_data(),
-        );
-        link.sleep_for(1_000_000_000 * 60 * 15);
-    }
+/// Summary vector epidemic routing: every requested message is handed over, with the
+/// acknowledgement layer chosen by the flavour. Nodes ping every 15 minutes, request from the same
+/// neighbour at most once per reconnect time and keep the messages delivered to them.
+pub type Epidemic<F> = LayeredRouter<Flooding<F>>;
 
-    pub fn handle_ping_packet(
-        &mut self,
-        link: &mut RouterLink,
-        flavour: &F,
-        source_id: NodeId,
-        mut summary_vector: BitVec,
-    ) {
-        if summary_vector.any() {
-            let should_reply = self
-                .last_ping
-                .get(&source_id)
-                .map_or(true, |last_fulfillment| {
-                    link.get_time() - last_fulfillment > self.reconnect_time
-                });
-            if should_reply {
-                let mut request_vector = self.summary_vector.clone();
-                request_vector.negate();
-                request_vector.and(&summary_vector);
-                // summary_vector.and(&request_vector);
-                link.add_to_transmit_buffer(
-                    Packet::<F::PingPacket, F::RequestPacket, F::FulfillmentPacket>::Request(
-                        flavour.make_request_packet(
-                            self.node_id.unwrap(),
-                            source_id,
-                            request_vector,
-                        ),
-                    )
-                        .as_data(),
-                );
-            }
-        }
-    }
+/// Hands every requested message over. `F` only tells apart routers built from different
+/// flavours.
+pub struct Flooding<F = ()> {
+    flavour: PhantomData<F>,
+}
 
-    pub fn handle_request_packet(
-        &mut self,
-        link: &mut RouterLink,
-        flavour: &mut F,
-        source_id: NodeId,
-        destination_id: NodeId,
-        request_vector: &BitVec,
-    ) {
-        if destination_id == self.node_id.unwrap() {
-            let requested_message_id_hashes = self
-                .message_table
-                .keys()
-                .filter(|&message_id_hash| request_vector.get(*message_id_hash).unwrap())
-                .copied()
-                .collect::<Vec<_>>();
-
-            let messages = requested_message_id_hashes
-                .iter()
-                .filter_map(|message_id_hash| {
-                    if let Some(&message_handle) = self.message_table.get(message_id_hash) {
-                        if self.verify_ttl(link, &message_handle) {
-                            flavour.on_ttl_evict(*message_id_hash)
-                        }
-                        if let Some(message_data) = link.get_from_message_buffer(&message_handle) {
-                            let message = Message::from_data(message_data);
-                            link.report_message_sent(message.id, source_id);
-                            return Some(message);
-                        }
-                    }
-                    None
-                })
-                .collect::<Vec<_>>();
-
-            if messages.len() > 0 {
-                link.add_to_transmit_buffer(
-                    Packet::<F::PingPacket, F::RequestPacket, F::FulfillmentPacket>::Fulfillment(
-                        flavour.make_fulfillment_packet(self.node_id.unwrap(), source_id, messages),
-                    )
-                        .as_data(),
-                );
-            }
+impl<F> Default for Flooding<F> {
+    fn default() -> Self {
+        Self {
+            flavour: PhantomData,
         }
     }
+}
 
-    pub fn handle_fulfillment_packet(
-        &mut self,
-        link: &mut RouterLink,
-        flavour: &mut F,
-        source_id: NodeId,
-        destination_id: NodeId,
-        messages: &Vec<Message>,
-    ) {
-        if destination_id == self.node_id.unwrap() {
-            for message in messages {
-                link.report_message_received(message.id, source_id);
-                self.insert_message(link, flavour, message.id, message.as_data())
-            }
-        }
+impl<F> Clone for Flooding<F> {
+    fn clone(&self) -> Self {
+        Self::default()
     }
+}
 
-    fn get_message_id_hash(&self, message_id: MessageId) -> usize {
-        let mut hasher = DefaultHasher::default();
-        hasher.write(message_id.to_le_bytes().borrow());
-        hasher.finish() as usize % self.summary_vector.len()
-        // message_id as usize % self.summary_vector.len()
-    }
+impl<F: 'static> ForwardingStrategy for Flooding<F> {
+    type CopyState = ();
 
-    fn insert_message(
-        &mut self,
-        link: &mut RouterLink,
-        flavour: &mut F,
-        id: MessageId,
-        data: Data,
-    ) {
-        let hash = self.get_message_id_hash(id);
-        let message = Message::from_data(&data);
-        if !self.summary_vector.get(hash).unwrap() {
-            let at_destination = message.destination.is_included(&self.node_id.unwrap());
-            if at_destination {
-                link.report_message_delivered(message.id, message.source_id);
-                flavour.on_message_delivered(hash);
-            }
-            self.summary_vector.set(hash, true);
-            if let Some(message_handle) = link.add_to_message_buffer(data) {
-                self.message_table.insert(hash, message_handle);
-            } else {
-                link.report_message_dropped(id);
-            }
-        } else {
-            link.report_message_dropped(id);
-        }
-    }
+    fn on_message_created(&mut self, _message: &Message) {}
 
-    fn verify_ttl(&mut self, link: &mut RouterLink, message_handle: &MessageHandle) -> bool {
-        if let Some(message_data) = link.get_from_message_buffer(message_handle) {
-            let message = Message::from_data(message_data);
-            if let Some(ttl) = message.ttl {
-                if ttl <= link.get_time() {
-                    link.remove_from_message_buffer(&message_handle);
-                    let message_id_hash = self.get_message_id_hash(message.id);
-                    self.message_table.remove(&message_id_hash);
-                    self.summary_vector.set(message_id_hash, false);
-                    return true;
-                }
-            }
-        }
-        false
+    fn on_forward(&mut self, _message: &Message, _state: &mut (), _neighbour_id: NodeId, _groups: &Groups) -> Option<()> {
+        Some(())
     }
 }
 
-impl<F: Flavour> Router for Epidemic<F> {
-    fn on_init(&mut self, link: &mut RouterLink, id: NodeId) {
-        self.node_id = Some(id);
-        link.sleep_for(random::<TimeMetric>() % (1_000_000_000 * 60 * 15))
-    }
-
-    fn on_message_created(
-        &mut self,
-        link: &mut RouterLink,
-        destination: MessageDestination<NodeId>,
-        payload: Data,
-        ttl: Option<TimeMetric>,
-    ) {
-        let message_id = (self.node_id.unwrap() as MessageId)
-            .overflowing_shl((NodeId::bytes() as u32) * 8)
-            .0
-            | self.next_message_index as MessageId;
-        self.next_message_index += 1;
-        link.report_message_created(message_id, destination.clone(), ttl);
-        let message = Message {
-            id: message_id,
-            source_id: self.node_id.unwrap(),
-            destination,
-            payload,
-            ttl,
-        };
-        let mut flavour = self.flavour.take().unwrap();
-        self.insert_message(link, &mut flavour, message_id, message.as_data());
-        self.flavour = Some(flavour);
+impl<F: Flavour> LayeredRouter<Flooding<F>> {
+    pub fn new(summary_vector: impl Into<BloomParams>, reconnect_time: TimeMetric) -> Self {
+        Self::with_strategy(Flooding::default(), Layers {
+            summary_vector: summary_vector.into(),
+            beacon_interval: PING_INTERVAL,
+            ping_on_receive: false,
+            ack: F::default().ack(),
+            bilateral_fulfillment: false,
+        })
+            .throttle_requests(reconnect_time)
+            .keep_delivered()
     }
+}
 
-    fn on_data_received(&mut self, link: &mut RouterLink, data: Data) {
-        let mut flavour = self.flavour.take().unwrap();
-        match Packet::from_data(&data) {
-            Packet::Ping(packet) => {
-                flavour.handle_ping_packet(self, link, packet);
+#[cfg(test)]
+mod tests {
+    use cgmath::Point3;
+
+    use crate::event::Event;
+    use crate::events::create_message_event::CreateMessageEvent;
+    use crate::events::router_event::{RouterEvent, RouterEventType};
+    use crate::ipn_sim::ipn_sim::IpnSim;
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::message_destination::MessageDestination;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::node::node_builder::NodeBuilder;
+    use crate::report::Report;
+    use crate::routers::epidemic::epidemic::Epidemic;
+    use crate::routers::epidemic::flavours::vanilla::Vanilla;
+    use crate::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
+    use crate::utils::shared;
+
+    /// Counts the messages sent to the second node.
+    #[derive(Default)]
+    struct SentCount(usize);
+
+    impl Report for SentCount {
+        fn on_tick(&mut self, _sim: &IpnSim, events: &Vec<Box<dyn Event>>) {
+            for event in events {
+                if let Some(router_event) = event.downcast_ref::<RouterEvent>() {
+                    if let RouterEventType::MessageSent { destination_node, .. } = &router_event.event_type {
+                        if destination_node.borrow().id == 1 {
+                            self.0 += 1;
+                        }
+                    }
+                }
             }
-            Packet::Request(packet) => flavour.handle_request_packet(self, link, packet),
-            Packet::Fulfillment(packet) => flavour.handle_fulfillment_packet(self, link, packet),
         }
-        self.flavour = Some(flavour);
     }
 
-    fn on_awake(&mut self, link: &mut RouterLink) {
-        let handles_to_verify = self.message_table.values().copied().collect::<Vec<_>>();
-        for message_handle in handles_to_verify {
-            self.verify_ttl(link, &message_handle);
+    #[test]
+    fn test_requests_throttled_and_deliveries_kept() {
+        let hour = 1_000_000_000 * 3600;
+        let sent_count = shared(SentCount::default());
+        let mut builder = IpnSimBuilder::new(2 * hour).add_shared_report(&sent_count);
+        // The source, a neighbour too small to hold the message, which keeps requesting it from
+        // both others, and the destination.
+        for &(x, message_buffer_size) in &[(0., 1024), (1., 1), (2., 1024)] {
+            builder = builder.add_node(NodeBuilder::new()
+                .name("node")
+                .movement(StaticMovement::new(Point3::new(x, 0., 0.)))
+                .message_buffer_size(message_buffer_size)
+                .router(Epidemic::<Vanilla>::new(64, hour))
+                .transceive_speed(1.)
+                .transceive_guard(SimpleTransceiveGuard::new(10.)));
         }
-        self.ping(link);
+        let source = builder.get_node(0);
+        let mut sim = builder
+            .add_event(0, CreateMessageEvent {
+                node: source,
+                destination: MessageDestination::Single(2),
+                payload: vec![0; 8].into_boxed_slice(),
+                ttl: None,
+            })
+            .build();
+        sim.run();
+
+        // Pinged every 15 minutes by each, the neighbour requests the message from each at most
+        // once an hour.
+        assert!((2..=6).contains(&sent_count.borrow().0), "{} sent", sent_count.borrow().0);
+        // The destination keeps the delivered message.
+        assert!(sim.get_node(2).borrow().message_buffer.get_occupancy() > 0.);
     }
 }
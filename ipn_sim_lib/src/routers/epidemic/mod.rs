@@ -1,5 +1,3 @@
 pub mod epidemic;
 pub mod flavour;
 pub mod flavours;
-pub mod message;
-pub mod packet;
@@ -1,3 +1 @@
 pub mod epidemic;
-pub mod message;
-pub mod packet;
\ No newline at end of file
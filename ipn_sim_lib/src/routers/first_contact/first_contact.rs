@@ -0,0 +1,48 @@
+use crate::ipn_sim::groups::Groups;
+use crate::router::components::ack_vector::Ack;
+use crate::router::components::bloom_filter::BloomParams;
+use crate::router::layered::forwarding_strategy::ForwardingStrategy;
+use crate::router::layered::layered_router::{LayeredRouter, Layers};
+use crate::router::layered::message::Message;
+use crate::utils::{NodeId, TimeMetric};
+
+/// Lower bound baseline: a single copy of every message is handed to the first neighbour to
+/// request it, never returning to a node it has already visited.
+pub type FirstContact = LayeredRouter<SingleCopy>;
+
+#[derive(Clone)]
+pub struct SingleCopy;
+
+impl ForwardingStrategy for SingleCopy {
+    /// Nodes the copy has visited.
+    type CopyState = Vec<NodeId>;
+
+    fn on_message_created(&mut self, message: &Message) -> Vec<NodeId> {
+        vec![message.source_id]
+    }
+
+    fn on_forward(&mut self, _message: &Message, path: &mut Vec<NodeId>, neighbour_id: NodeId, _groups: &Groups) -> Option<Vec<NodeId>> {
+        if path.contains(&neighbour_id) {
+            None
+        } else {
+            path.push(neighbour_id);
+            Some(path.clone())
+        }
+    }
+
+    fn retain(&self, _path: &Vec<NodeId>) -> bool {
+        false
+    }
+}
+
+impl LayeredRouter<SingleCopy> {
+    pub fn new(summary_vector: impl Into<BloomParams>, beacon_interval: TimeMetric) -> Self {
+        Self::with_strategy(SingleCopy, Layers {
+            summary_vector: summary_vector.into(),
+            beacon_interval,
+            ping_on_receive: true,
+            ack: Ack::None,
+            bilateral_fulfillment: false,
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::utils::NodeId;
+
+/// How likely this node is to meet each other node next, along with the likelihoods learned from
+/// the nodes it has met, from which path costs are derived.
+#[derive(Clone, Default)]
+pub struct MeetingLikelihoods {
+    own: FxHashMap<NodeId, f64>,
+    learned: FxHashMap<NodeId, FxHashMap<NodeId, f64>>,
+}
+
+impl MeetingLikelihoods {
+    /// Incremental averaging: the met node's likelihood is raised by one and all are renormalized.
+    pub fn on_meeting(&mut self, node_id: NodeId) {
+        *self.own.entry(node_id).or_insert(0.) += 1.;
+        let total = self.own.values().sum::<f64>();
+        for likelihood in self.own.values_mut() {
+            *likelihood /= total;
+        }
+    }
+
+    pub fn own(&self) -> Vec<(NodeId, f64)> {
+        self.own.iter().map(|(&node_id, &likelihood)| (node_id, likelihood)).collect()
+    }
+
+    pub fn learn(&mut self, node_id: NodeId, likelihoods: Vec<(NodeId, f64)>) {
+        self.learned.insert(node_id, likelihoods.into_iter().collect());
+    }
+
+    /// Cost of the cheapest path from `own_id` to every reachable node, where moving from one node
+    /// to the next costs the likelihood of them not meeting.
+    pub fn path_costs(&self, own_id: NodeId) -> FxHashMap<NodeId, f64> {
+        let mut costs = FxHashMap::default();
+        let mut visited = FxHashSet::default();
+        costs.insert(own_id, 0.);
+
+        while let Some((node_id, cost)) = costs
+            .iter()
+            .filter(|(node_id, _)| !visited.contains(*node_id))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(&node_id, &cost)| (node_id, cost)) {
+            visited.insert(node_id);
+
+            let likelihoods = if node_id == own_id {
+                Some(&self.own)
+            } else {
+                self.learned.get(&node_id)
+            };
+
+            for (&next_id, &likelihood) in likelihoods.into_iter().flatten() {
+                let next_cost = cost + 1. - likelihood;
+                if costs.get(&next_id).is_none_or(|&current_cost| next_cost < current_cost) {
+                    costs.insert(next_id, next_cost);
+                }
+            }
+        }
+
+        costs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routers::max_prop::likelihoods::MeetingLikelihoods;
+
+    #[test]
+    fn test_path_costs() {
+        let mut likelihoods = MeetingLikelihoods::default();
+        likelihoods.on_meeting(1);
+        likelihoods.on_meeting(2);
+        likelihoods.on_meeting(1);
+        likelihoods.learn(1, vec![(3, 1.)]);
+        likelihoods.learn(2, vec![(3, 0.5)]);
+
+        let costs = likelihoods.path_costs(0);
+        assert!((costs[&1] - 0.25).abs() < 1e-9);
+        assert!((costs[&2] - 0.75).abs() < 1e-9);
+        assert!((costs[&3] - 0.25).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,189 @@
+use std::cmp::Ordering;
+
+use bit_vec::BitVec;
+use rustc_hash::FxHashMap;
+
+use crate::binary_serde::BinarySerde;
+use crate::ipn_sim::groups::Groups;
+use crate::message_destination::MessageDestination;
+use crate::router::components::ack_vector::AckVector;
+use crate::router::components::beacon::Beacon;
+use crate::router::components::bloom_filter::BloomParams;
+use crate::router::components::message_store::MessageStore;
+use crate::router::layered::message::Message;
+use crate::router::PacketRouter;
+use crate::router_link::RouterLink;
+use crate::routers::max_prop::likelihoods::MeetingLikelihoods;
+use crate::routers::max_prop::packet::Packet;
+use crate::utils::{Data, NodeId, TimeMetric};
+use crate::wire_size::WireSize;
+
+/// MaxProp: hello beacons carry the summary vector, acknowledgements and meeting likelihoods of
+/// their source. Messages the encountered node lacks are sent one per packet, destination first,
+/// then those which have taken fewer than `hop_threshold` hops by hop count, then the rest by the
+/// cost of delivering them.
+#[derive(Clone)]
+pub struct MaxProp {
+    beacon: Beacon,
+    store: MessageStore<u16>,
+    ack_vector: Option<AckVector>,
+    likelihoods: MeetingLikelihoods,
+    hop_threshold: u16,
+}
+
+impl MaxProp {
+    pub fn new(summary_vector: impl Into<BloomParams>, beacon_interval: TimeMetric, hop_threshold: u16, propagate_acks: bool) -> Self {
+        let summary_vector = summary_vector.into();
+        Self {
+            beacon: Beacon::new(beacon_interval),
+            store: MessageStore::new(summary_vector),
+            ack_vector: propagate_acks.then(|| AckVector::new(summary_vector)),
+            likelihoods: MeetingLikelihoods::default(),
+            hop_threshold,
+        }
+    }
+
+    pub fn likelihoods(&self) -> &MeetingLikelihoods {
+        &self.likelihoods
+    }
+
+    fn insert_message(&mut self, link: &mut RouterLink, message: Message, hop_count: u16) {
+        if self.store.has_seen(message.id) {
+            link.report_message_dropped(message.id);
+        } else if link.is_destination(&message.destination, link.get_node_id()) {
+            link.report_message_delivered(message.id, message.source_id);
+            self.store.mark_seen(message.id);
+
+            if let Some(ack_vector) = &mut self.ack_vector {
+                ack_vector.acknowledge(message.id);
+            }
+
+            link.report_message_dropped(message.id);
+        } else {
+            let size = message.wire_size();
+            if !self.store.insert(link, message.id, message.ttl, message.as_data(), size, hop_count) {
+                link.report_message_dropped(message.id);
+            }
+        }
+    }
+
+    fn delivery_cost(destination: &MessageDestination<NodeId>, groups: &Groups, path_costs: &FxHashMap<NodeId, f64>) -> f64 {
+        let cost = |id: &NodeId| path_costs.get(id).copied().unwrap_or(f64::INFINITY);
+        match destination.node_ids(groups) {
+            None => 0.,
+            Some(ids) => ids.iter().map(cost).fold(f64::INFINITY, f64::min),
+        }
+    }
+
+    fn select_messages(
+        &mut self,
+        link: &mut RouterLink,
+        neighbour_id: NodeId,
+        neighbour_summary_vector: &BitVec,
+    ) -> Vec<(Message, u16)> {
+        let path_costs = self.likelihoods.path_costs(link.get_node_id());
+
+        let mut messages = vec![];
+        for id in self.store.missing_from(link, neighbour_id, neighbour_summary_vector) {
+            if self.store.verify_ttl(link, id) {
+                continue;
+            }
+            let stored_message = self.store.get(id).unwrap();
+            let hop_count = stored_message.state;
+            let message = Message::from_data(link.get_from_message_buffer(&stored_message.handle).unwrap());
+
+            let order = if link.is_destination(&message.destination, neighbour_id) {
+                (0, 0.)
+            } else if hop_count < self.hop_threshold {
+                (1, hop_count as f64)
+            } else {
+                (2, Self::delivery_cost(&message.destination, link.groups(), &path_costs))
+            };
+            messages.push((order, message, hop_count + 1));
+        }
+
+        messages.sort_by(|(a, ..), (b, ..)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        messages.into_iter().map(|(_, message, hop_count)| (message, hop_count)).collect()
+    }
+}
+
+impl PacketRouter for MaxProp {
+    type Packet = Packet;
+
+    fn on_init(&mut self, link: &mut RouterLink, _id: NodeId) {
+        self.beacon.on_init(link);
+    }
+
+    fn on_message_created(
+        &mut self,
+        link: &mut RouterLink,
+        destination: MessageDestination<NodeId>,
+        payload: Data,
+        ttl: Option<TimeMetric>,
+    ) {
+        let message_id = self.store.next_message_id(link.get_node_id());
+
+        link.report_message_created(message_id, destination.clone(), ttl);
+
+        self.insert_message(link, Message {
+            id: message_id,
+            source_id: link.get_node_id(),
+            destination,
+            payload,
+            ttl,
+        }, 0);
+    }
+
+    fn on_packet_received(&mut self, link: &mut RouterLink, packet: Packet) {
+        match packet {
+            Packet::Hello { source_id, summary_vector, ack_vector, likelihoods } => {
+                self.likelihoods.on_meeting(source_id);
+                self.likelihoods.learn(source_id, likelihoods);
+
+                if let (Some(own_ack_vector), Some(ack_vector)) = (&mut self.ack_vector, &ack_vector) {
+                    own_ack_vector.merge(link, &mut self.store, ack_vector);
+                }
+
+                let messages = self.select_messages(link, source_id, &summary_vector);
+
+                for (message, hop_count) in messages {
+                    link.report_message_sent(message.id, source_id);
+
+                    // The first member of an anycast group to get the message consumes it.
+                    if message.destination.is_anycast() && link.is_destination(&message.destination, source_id) {
+                        self.store.remove(link, message.id);
+                        link.report_message_dropped(message.id);
+                    }
+
+                    link.send_packet_to(Packet::Message {
+                        source_id: link.get_node_id(),
+                        destination_id: source_id,
+                        message,
+                        hop_count,
+                    }, source_id);
+                }
+            }
+            Packet::Message { source_id, destination_id, message, hop_count } => {
+                if destination_id == link.get_node_id() {
+                    link.report_message_received(message.id, source_id);
+                    self.insert_message(link, message, hop_count);
+                }
+            }
+        }
+    }
+
+    fn on_awake(&mut self, link: &mut RouterLink) {
+        self.store.evict_expired(link);
+
+        if self.beacon.is_due(link) {
+            link.send_packet(Packet::Hello {
+                source_id: link.get_node_id(),
+                summary_vector: self.store.summary_vector().bits().clone(),
+                ack_vector: self.ack_vector.as_ref().map(|ack_vector| ack_vector.bits().clone()),
+                likelihoods: self.likelihoods.own(),
+            });
+
+            self.beacon.mark_sent(link);
+        }
+    }
+}
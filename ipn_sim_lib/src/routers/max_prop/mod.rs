@@ -0,0 +1,3 @@
+pub mod max_prop;
+pub mod likelihoods;
+pub mod packet;
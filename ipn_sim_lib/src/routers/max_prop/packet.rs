@@ -0,0 +1,64 @@
+use bit_vec::BitVec;
+use serde::{Deserialize, Serialize};
+
+use crate::binary_serde::BinarySerde;
+use crate::packet::{Packet as PacketTrait, PacketSize};
+use crate::router::layered::message::Message;
+use crate::utils::NodeId;
+use crate::wire_size::{WireSize, TAG_SIZE};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum Packet {
+    /// Beacon carrying the summary vector, acknowledgements and meeting likelihoods of its source.
+    Hello {
+        source_id: NodeId,
+        summary_vector: BitVec,
+        ack_vector: Option<BitVec>,
+        likelihoods: Vec<(NodeId, f64)>,
+    },
+    /// A single message with the number of hops it has taken. Messages are sent one per packet in
+    /// priority order, so a contact ending early cuts off the lowest priority messages.
+    Message {
+        source_id: NodeId,
+        destination_id: NodeId,
+        message: Message,
+        hop_count: u16,
+    },
+}
+
+impl BinarySerde for Packet {}
+
+impl PacketTrait for Packet {
+    const SHARED: bool = true;
+
+    fn packet_type(&self) -> &'static str {
+        match self {
+            Packet::Hello { .. } => "Hello",
+            Packet::Message { .. } => "Message",
+        }
+    }
+
+    fn packet_size(&self) -> Option<PacketSize> {
+        Some(match self {
+            Packet::Hello { source_id, summary_vector, ack_vector, likelihoods } => PacketSize::header(
+                TAG_SIZE
+                    + source_id.wire_size()
+                    + summary_vector.wire_size()
+                    + ack_vector.wire_size()
+                    + likelihoods.wire_size()
+            ),
+            Packet::Message { source_id, destination_id, message, hop_count } => {
+                let payload = message.payload.len();
+                PacketSize::new(
+                    TAG_SIZE
+                        + source_id.wire_size()
+                        + destination_id.wire_size()
+                        + message.wire_size()
+                        + hop_count.wire_size()
+                        - payload,
+                    payload,
+                )
+            }
+        })
+    }
+}
@@ -1,6 +1,11 @@
-// pub mod spray_and_wait;
 pub mod epidemic;
 pub mod test_router;
-pub mod source_spray_and_wait;
-pub mod source_spray_and_wait_2;
+pub mod spray_and_wait;
 pub mod epidemic_2;
+pub mod oracle_shortest_path;
+pub mod prophet;
+pub mod cgr;
+pub mod max_prop;
+pub mod direct_delivery;
+pub mod first_contact;
+pub mod custody_relay;
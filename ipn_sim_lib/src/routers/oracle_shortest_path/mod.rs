@@ -0,0 +1,2 @@
+pub mod oracle_shortest_path;
+pub mod packet;
@@ -0,0 +1,174 @@
+use rustc_hash::FxHashMap;
+
+use crate::binary_serde::BinarySerde;
+use crate::message_destination::MessageDestination;
+use crate::node::message_buffer::MessageHandle;
+use crate::oracle::contact::earliest_arrival;
+use crate::router::layered::message::Message;
+use crate::router::PacketRouter;
+use crate::router_link::RouterLink;
+use crate::routers::oracle_shortest_path::packet::Packet;
+use crate::utils;
+use crate::utils::{Data, NodeId, TimeMetric};
+use crate::wire_size::WireSize;
+
+/// A single copy benchmark router that forwards each message along the earliest arrival route over
+/// the future contact graph, recomputing the route at every hop. Requires oracle access.
+#[derive(Clone)]
+pub struct OracleShortestPath {
+    contact_resolution: TimeMetric,
+    held_messages: FxHashMap<MessageHandle, HeldMessage>,
+    next_message_index: u16,
+}
+
+#[derive(Clone)]
+struct HeldMessage {
+    target_id: NodeId,
+    next_hop_id: NodeId,
+    departure_time: TimeMetric,
+}
+
+impl OracleShortestPath {
+    pub fn new(contact_resolution: TimeMetric) -> Self {
+        Self {
+            contact_resolution,
+            held_messages: FxHashMap::default(),
+            next_message_index: 0,
+        }
+    }
+
+    /// Holds a copy of the message bound for `target_id` until the next hop on its route departs.
+    fn route(&mut self, link: &mut RouterLink, message: &Message, target_id: NodeId) {
+        let contact_resolution = self.contact_resolution;
+        let node_id = link.get_node_id();
+        let time = link.get_time();
+
+        let contacts = link
+            .oracle()
+            .expect("OracleShortestPath requires NodeBuilder::oracle_access")
+            .future_contacts(contact_resolution);
+
+        let first_hop = earliest_arrival(&contacts, node_id, target_id, time)
+            .and_then(|route| route.hops.into_iter().next());
+
+        if let Some(first_hop) = first_hop {
+            if message.ttl.is_some_and(|ttl| ttl <= first_hop.departure_time) {
+                link.report_message_dropped(message.id);
+                return;
+            }
+            if let Some(handle) = link.add_sized_to_message_buffer(message.as_data(), message.wire_size()) {
+                self.held_messages.insert(handle, HeldMessage {
+                    target_id,
+                    next_hop_id: first_hop.node_id,
+                    departure_time: first_hop.departure_time,
+                });
+                link.sleep_for(first_hop.departure_time - time);
+                return;
+            }
+        }
+        link.report_message_dropped(message.id);
+    }
+
+    /// The anycast target reachable soonest.
+    fn nearest_target(&self, link: &mut RouterLink, target_ids: Vec<NodeId>) -> Option<NodeId> {
+        let node_id = link.get_node_id();
+        let time = link.get_time();
+
+        let contacts = link
+            .oracle()
+            .expect("OracleShortestPath requires NodeBuilder::oracle_access")
+            .future_contacts(self.contact_resolution);
+
+        target_ids
+            .into_iter()
+            .filter(|&target_id| target_id != node_id)
+            .filter_map(|target_id| {
+                earliest_arrival(&contacts, node_id, target_id, time).map(|route| (route.arrival_time, target_id))
+            })
+            .min()
+            .map(|(_, target_id)| target_id)
+    }
+}
+
+impl PacketRouter for OracleShortestPath {
+    type Packet = Packet;
+
+    fn on_message_created(
+        &mut self,
+        link: &mut RouterLink,
+        destination: MessageDestination<NodeId>,
+        payload: Data,
+        ttl: Option<TimeMetric>,
+    ) {
+        let message_id = utils::next_message_id(link.get_node_id(), &mut self.next_message_index);
+
+        link.report_message_created(message_id, destination.clone(), ttl);
+
+        let node_id = link.get_node_id();
+        let node_count = link
+            .oracle()
+            .expect("OracleShortestPath requires NodeBuilder::oracle_access")
+            .node_count() as NodeId;
+        let mut target_ids = destination
+            .node_ids(link.groups())
+            .unwrap_or_else(|| (0..node_count).collect());
+        if destination.is_anycast() {
+            target_ids = self.nearest_target(link, target_ids).into_iter().collect();
+        }
+
+        let message = Message {
+            id: message_id,
+            source_id: node_id,
+            destination,
+            payload,
+            ttl,
+        };
+
+        for target_id in target_ids {
+            if target_id != node_id {
+                self.route(link, &message, target_id);
+            }
+        }
+    }
+
+    fn on_packet_received(&mut self, link: &mut RouterLink, packet: Packet) {
+        if packet.destination_id != link.get_node_id() {
+            return;
+        }
+        link.report_message_received(packet.message.id, packet.source_id);
+
+        if packet.target_id == link.get_node_id() && link.is_destination(&packet.message.destination, link.get_node_id()) {
+            link.report_message_delivered(packet.message.id, packet.message.source_id);
+            link.report_message_dropped(packet.message.id);
+        } else {
+            self.route(link, &packet.message, packet.target_id);
+        }
+    }
+
+    fn on_awake(&mut self, link: &mut RouterLink) {
+        let due_handles = self.held_messages
+            .iter()
+            .filter(|(_, held_message)| held_message.departure_time <= link.get_time())
+            .map(|(handle, _)| *handle)
+            .collect::<Vec<_>>();
+
+        for handle in due_handles {
+            let held_message = self.held_messages.remove(&handle).unwrap();
+            let message = Message::from_data(&link.remove_from_message_buffer(&handle).unwrap());
+
+            if message.ttl.is_some_and(|ttl| ttl <= link.get_time()) {
+                link.report_message_dropped(message.id);
+                continue;
+            }
+
+            link.report_message_sent(message.id, held_message.next_hop_id);
+            link.report_message_dropped(message.id);
+            link.send_packet_to(Packet {
+                source_id: link.get_node_id(),
+                destination_id: held_message.next_hop_id,
+                target_id: held_message.target_id,
+                message,
+            }, held_message.next_hop_id);
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::binary_serde::BinarySerde;
+use crate::packet::{Packet as PacketTrait, PacketSize};
+use crate::router::layered::message::Message;
+use crate::utils::NodeId;
+use crate::wire_size::WireSize;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Packet {
+    pub source_id: NodeId,
+    pub destination_id: NodeId,
+    pub target_id: NodeId,
+    pub message: Message,
+}
+
+impl BinarySerde for Packet {}
+
+impl PacketTrait for Packet {
+    fn packet_size(&self) -> Option<PacketSize> {
+        Some(PacketSize::new(
+            self.source_id.wire_size()
+                + self.destination_id.wire_size()
+                + self.target_id.wire_size()
+                + self.message.header_size(),
+            self.message.payload.len(),
+        ))
+    }
+}
@@ -0,0 +1,5 @@
+pub mod prophet;
+pub mod predictabilities;
+pub mod packet;
+pub mod strategy;
+pub mod strategies;
@@ -0,0 +1,52 @@
+use bit_vec::BitVec;
+use serde::{Deserialize, Serialize};
+
+use crate::binary_serde::BinarySerde;
+use crate::packet::{Packet as PacketTrait, PacketSize};
+use crate::router::layered::message::Message;
+use crate::utils::NodeId;
+use crate::wire_size::{WireSize, TAG_SIZE};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum Packet {
+    /// Beacon carrying the summary vector and delivery predictabilities of its source.
+    Hello {
+        source_id: NodeId,
+        summary_vector: BitVec,
+        predictabilities: Vec<(NodeId, f64)>,
+    },
+    /// A single message, so a contact ending early cuts off the lowest priority messages.
+    Message {
+        source_id: NodeId,
+        destination_id: NodeId,
+        message: Message,
+    },
+}
+
+impl BinarySerde for Packet {}
+
+impl PacketTrait for Packet {
+    const SHARED: bool = true;
+
+    fn packet_type(&self) -> &'static str {
+        match self {
+            Packet::Hello { .. } => "Hello",
+            Packet::Message { .. } => "Message",
+        }
+    }
+
+    fn packet_size(&self) -> Option<PacketSize> {
+        Some(match self {
+            Packet::Hello { source_id, summary_vector, predictabilities } => PacketSize::header(
+                TAG_SIZE + source_id.wire_size() + summary_vector.wire_size() + predictabilities.wire_size()
+            ),
+            Packet::Message { source_id, destination_id, message } => {
+                let payload = message.payload.len();
+                PacketSize::new(
+                    TAG_SIZE + source_id.wire_size() + destination_id.wire_size() + message.wire_size() - payload,
+                    payload,
+                )
+            }
+        })
+    }
+}
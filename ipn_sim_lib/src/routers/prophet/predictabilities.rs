@@ -0,0 +1,101 @@
+use rustc_hash::FxHashMap;
+
+use crate::utils::{NodeId, TimeMetric};
+
+#[derive(Clone)]
+pub struct PredictabilityParams {
+    pub p_encounter: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    /// Time over which predictabilities are aged by one factor of `gamma`.
+    pub aging_time_unit: TimeMetric,
+}
+
+impl Default for PredictabilityParams {
+    fn default() -> Self {
+        Self {
+            p_encounter: 0.75,
+            beta: 0.25,
+            gamma: 0.98,
+            aging_time_unit: 1_000_000_000 * 60,
+        }
+    }
+}
+
+/// This node's delivery predictability for every destination it has learned of.
+#[derive(Clone)]
+pub struct Predictabilities {
+    params: PredictabilityParams,
+    values: FxHashMap<NodeId, f64>,
+    last_aged: TimeMetric,
+}
+
+impl Predictabilities {
+    pub fn new(params: PredictabilityParams) -> Self {
+        Self {
+            params,
+            values: FxHashMap::default(),
+            last_aged: 0,
+        }
+    }
+
+    pub fn get(&self, node_id: NodeId) -> f64 {
+        self.values.get(&node_id).copied().unwrap_or(0.)
+    }
+
+    pub fn values(&self) -> Vec<(NodeId, f64)> {
+        self.values.iter().map(|(&node_id, &value)| (node_id, value)).collect()
+    }
+
+    /// Decays every predictability by `gamma` for each whole time unit elapsed since last aged.
+    pub fn age(&mut self, time: TimeMetric) {
+        let time_units = (time - self.last_aged) / self.params.aging_time_unit;
+        if time_units > 0 {
+            let factor = self.params.gamma.powi(time_units as i32);
+            for value in self.values.values_mut() {
+                *value *= factor;
+            }
+            self.last_aged += time_units * self.params.aging_time_unit;
+        }
+    }
+
+    pub fn on_encounter(&mut self, node_id: NodeId) {
+        let value = self.values.entry(node_id).or_insert(0.);
+        *value += (1. - *value) * self.params.p_encounter;
+    }
+
+    /// Learns of the destinations the encountered node `via_id` can reach.
+    pub fn on_transitive(&mut self, own_id: NodeId, via_id: NodeId, via_values: &[(NodeId, f64)]) {
+        let via_predictability = self.get(via_id);
+        for &(node_id, via_value) in via_values {
+            if node_id != own_id && node_id != via_id {
+                let value = self.values.entry(node_id).or_insert(0.);
+                *value = value.max(via_predictability * via_value * self.params.beta);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routers::prophet::predictabilities::{Predictabilities, PredictabilityParams};
+
+    #[test]
+    fn test_predictabilities() {
+        let params = PredictabilityParams::default();
+        let aging_time_unit = params.aging_time_unit;
+        let mut predictabilities = Predictabilities::new(params);
+
+        predictabilities.on_encounter(1);
+        assert_eq!(predictabilities.get(1), 0.75);
+        predictabilities.on_encounter(1);
+        assert_eq!(predictabilities.get(1), 0.9375);
+
+        predictabilities.on_transitive(0, 1, &[(0, 1.), (2, 0.8)]);
+        assert_eq!(predictabilities.get(0), 0.);
+        assert_eq!(predictabilities.get(2), 0.9375 * 0.8 * 0.25);
+
+        predictabilities.age(aging_time_unit * 2 + 1);
+        assert_eq!(predictabilities.get(1), 0.9375 * 0.98 * 0.98);
+    }
+}
@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use bit_vec::BitVec;
+use rustc_hash::FxHashMap;
+
+use crate::binary_serde::BinarySerde;
+use crate::ipn_sim::groups::Groups;
+use crate::message_destination::MessageDestination;
+use crate::router::components::beacon::Beacon;
+use crate::router::components::bloom_filter::BloomParams;
+use crate::router::components::message_store::MessageStore;
+use crate::router::layered::message::Message;
+use crate::router::PacketRouter;
+use crate::router_link::RouterLink;
+use crate::routers::prophet::packet::Packet;
+use crate::routers::prophet::predictabilities::{Predictabilities, PredictabilityParams};
+use crate::routers::prophet::strategy::Strategy;
+use crate::utils::{Data, NodeId, TimeMetric};
+use crate::wire_size::WireSize;
+
+/// Probabilistic Routing Protocol using History of Encounters and Transitivity. Hello beacons carry
+/// the summary vector and delivery predictabilities of their source, and every node hands the
+/// encountered node the messages it lacks which the strategy deems worth forwarding, one packet each
+/// in the strategy's priority order.
+#[derive(Clone)]
+pub struct Prophet<S: Strategy> {
+    beacon: Beacon,
+    store: MessageStore<()>,
+    predictabilities: Predictabilities,
+    strategy: PhantomData<S>,
+}
+
+impl<S: Strategy> Prophet<S> {
+    pub fn new(summary_vector: impl Into<BloomParams>, beacon_interval: TimeMetric, params: PredictabilityParams) -> Self {
+        Self {
+            beacon: Beacon::new(beacon_interval),
+            store: MessageStore::new(summary_vector),
+            predictabilities: Predictabilities::new(params),
+            strategy: PhantomData,
+        }
+    }
+
+    pub fn predictabilities(&self) -> &Predictabilities {
+        &self.predictabilities
+    }
+
+    fn insert_message(&mut self, link: &mut RouterLink, message: Message) {
+        if self.store.has_seen(message.id) {
+            link.report_message_dropped(message.id);
+        } else if link.is_destination(&message.destination, link.get_node_id()) {
+            link.report_message_delivered(message.id, message.source_id);
+            self.store.mark_seen(message.id);
+            link.report_message_dropped(message.id);
+        } else {
+            let size = message.wire_size();
+            if !self.store.insert(link, message.id, message.ttl, message.as_data(), size, ()) {
+                link.report_message_dropped(message.id);
+            }
+        }
+    }
+
+    /// Best predictability towards any of the message's destinations.
+    fn destination_predictability(
+        destination: &MessageDestination<NodeId>,
+        groups: &Groups,
+        predictability: impl Fn(NodeId) -> f64,
+    ) -> f64 {
+        match destination.node_ids(groups) {
+            None => 1.,
+            Some(ids) => ids
+                .into_iter()
+                .map(predictability)
+                .fold(0., f64::max),
+        }
+    }
+
+    fn select_messages(
+        &mut self,
+        link: &mut RouterLink,
+        neighbour_id: NodeId,
+        neighbour_summary_vector: &BitVec,
+        neighbour_predictabilities: &FxHashMap<NodeId, f64>,
+    ) -> Vec<Message> {
+        let mut messages = vec![];
+        for id in self.store.missing_from(link, neighbour_id, neighbour_summary_vector) {
+            if self.store.verify_ttl(link, id) {
+                continue;
+            }
+            let message = Message::from_data(
+                link.get_from_message_buffer(&self.store.get(id).unwrap().handle).unwrap()
+            );
+
+            let own_predictability = Self::destination_predictability(
+                &message.destination,
+                link.groups(),
+                |id| self.predictabilities.get(id),
+            );
+            let neighbour_predictability = Self::destination_predictability(
+                &message.destination,
+                link.groups(),
+                |id| if id == neighbour_id { 1. } else { neighbour_predictabilities.get(&id).copied().unwrap_or(0.) },
+            );
+
+            if link.is_destination(&message.destination, neighbour_id)
+                || S::should_forward(own_predictability, neighbour_predictability) {
+                messages.push((S::priority(own_predictability, neighbour_predictability), message));
+            }
+        }
+
+        messages.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        messages.into_iter().map(|(_, message)| message).collect()
+    }
+}
+
+impl<S: Strategy> PacketRouter for Prophet<S> {
+    type Packet = Packet;
+
+    fn on_init(&mut self, link: &mut RouterLink, _id: NodeId) {
+        self.beacon.on_init(link);
+    }
+
+    fn on_message_created(
+        &mut self,
+        link: &mut RouterLink,
+        destination: MessageDestination<NodeId>,
+        payload: Data,
+        ttl: Option<TimeMetric>,
+    ) {
+        let message_id = self.store.next_message_id(link.get_node_id());
+
+        link.report_message_created(message_id, destination.clone(), ttl);
+
+        self.insert_message(link, Message {
+            id: message_id,
+            source_id: link.get_node_id(),
+            destination,
+            payload,
+            ttl,
+        });
+    }
+
+    fn on_packet_received(&mut self, link: &mut RouterLink, packet: Packet) {
+        match packet {
+            Packet::Hello { source_id, summary_vector, predictabilities } => {
+                self.predictabilities.age(link.get_time());
+                self.predictabilities.on_encounter(source_id);
+                self.predictabilities.on_transitive(link.get_node_id(), source_id, &predictabilities);
+
+                let neighbour_predictabilities = predictabilities.into_iter().collect();
+
+                let messages = self.select_messages(link, source_id, &summary_vector, &neighbour_predictabilities);
+
+                for message in messages {
+                    link.report_message_sent(message.id, source_id);
+
+                    // The first member of an anycast group to get the message consumes it.
+                    if message.destination.is_anycast() && link.is_destination(&message.destination, source_id) {
+                        self.store.remove(link, message.id);
+                        link.report_message_dropped(message.id);
+                    }
+
+                    link.send_packet_to(Packet::Message {
+                        source_id: link.get_node_id(),
+                        destination_id: source_id,
+                        message,
+                    }, source_id);
+                }
+            }
+            Packet::Message { source_id, destination_id, message } => {
+                if destination_id == link.get_node_id() {
+                    link.report_message_received(message.id, source_id);
+                    self.insert_message(link, message);
+                }
+            }
+        }
+    }
+
+    fn on_awake(&mut self, link: &mut RouterLink) {
+        self.store.evict_expired(link);
+
+        if self.beacon.is_due(link) {
+            self.predictabilities.age(link.get_time());
+
+            link.send_packet(Packet::Hello {
+                source_id: link.get_node_id(),
+                summary_vector: self.store.summary_vector().bits().clone(),
+                predictabilities: self.predictabilities.values(),
+            });
+
+            self.beacon.mark_sent(link);
+        }
+    }
+}
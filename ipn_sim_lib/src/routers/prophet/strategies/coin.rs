@@ -0,0 +1,15 @@
+use rand::random;
+
+use crate::routers::prophet::strategy::Strategy;
+
+/// Forwards with a probability equal to the encountered node's delivery predictability.
+#[derive(Clone)]
+pub struct Coin;
+
+impl Strategy for Coin {
+    const NAME: &'static str = "COIN";
+
+    fn should_forward(_own_predictability: f64, neighbour_predictability: f64) -> bool {
+        random::<f64>() < neighbour_predictability
+    }
+}
@@ -0,0 +1,13 @@
+use crate::routers::prophet::strategy::Strategy;
+
+/// Forwards when the encountered node is more likely to deliver the message.
+#[derive(Clone)]
+pub struct Grtr;
+
+impl Strategy for Grtr {
+    const NAME: &'static str = "GRTR";
+
+    fn should_forward(own_predictability: f64, neighbour_predictability: f64) -> bool {
+        neighbour_predictability > own_predictability
+    }
+}
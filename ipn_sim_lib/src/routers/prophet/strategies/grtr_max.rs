@@ -0,0 +1,18 @@
+use crate::routers::prophet::strategy::Strategy;
+
+/// Forwards like [`Grtr`](crate::routers::prophet::strategies::grtr::Grtr), sending the messages
+/// the encountered node is most likely to deliver first.
+#[derive(Clone)]
+pub struct GrtrMax;
+
+impl Strategy for GrtrMax {
+    const NAME: &'static str = "GRTRMax";
+
+    fn should_forward(own_predictability: f64, neighbour_predictability: f64) -> bool {
+        neighbour_predictability > own_predictability
+    }
+
+    fn priority(_own_predictability: f64, neighbour_predictability: f64) -> f64 {
+        neighbour_predictability
+    }
+}
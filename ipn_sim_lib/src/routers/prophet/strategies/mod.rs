@@ -0,0 +1,3 @@
+pub mod grtr;
+pub mod grtr_max;
+pub mod coin;
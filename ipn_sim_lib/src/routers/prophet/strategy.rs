@@ -0,0 +1,12 @@
+/// Decides whether a message is handed to an encountered node, given the delivery predictability
+/// of this node and of the encountered node for the message's destination.
+pub trait Strategy: Clone + 'static {
+    const NAME: &'static str;
+
+    fn should_forward(own_predictability: f64, neighbour_predictability: f64) -> bool;
+
+    /// Forwarded messages are queued highest priority first.
+    fn priority(_own_predictability: f64, _neighbour_predictability: f64) -> f64 {
+        0.
+    }
+}
@@ -1,3 +0,0 @@
-pub mod source_spray_and_wait;
-pub mod message;
-pub mod packet;
\ No newline at end of file
@@ -1,3 +0,0 @@
-pub mod source_spray_and_wait_2;
-pub mod packet;
-pub mod message;
\ No newline at end of file
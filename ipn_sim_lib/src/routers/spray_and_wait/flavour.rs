@@ -1,8 +1,5 @@
-use crate::binary_serde::BinarySerde;
-
-pub trait Flavour: Clone {
-    type PingPacket: BinarySerde;
-    type FulfillmentPacket: BinarySerde;
-
-    fn new() -> Self;
-}
\ No newline at end of file
+/// Decides how the copies of a message are divided when it is sprayed to a relay.
+pub trait Flavour: Clone + 'static {
+    /// Number of the `remaining_copies` (always more than one) handed to the relay.
+    fn handed_copies(&self, remaining_copies: u16) -> u16;
+}
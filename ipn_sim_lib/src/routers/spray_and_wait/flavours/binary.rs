@@ -1,12 +1,11 @@
-use crate::utils::{MessageId, NodeId};
+use crate::routers::spray_and_wait::flavour::Flavour;
 
-pub struct Binary {
-    messages_to_spray: Vec<MessageToSpray>
-}
+/// Every holder of more than one copy hands half of them to each relay it meets.
+#[derive(Clone)]
+pub struct Binary;
 
-struct MessageToSpray {
-    id: MessageId,
-    copies_remaining: u16,
-    sprayed_node_ids: Vec<NodeId>
+impl Flavour for Binary {
+    fn handed_copies(&self, remaining_copies: u16) -> u16 {
+        remaining_copies / 2
+    }
 }
-
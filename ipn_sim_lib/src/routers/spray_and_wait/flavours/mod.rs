@@ -1,2 +1,2 @@
 pub mod source;
-pub mod binary;
\ No newline at end of file
+pub mod binary;
@@ -1,40 +1,11 @@
-use crate::utils::{MessageId, NodeId};
 use crate::routers::spray_and_wait::flavour::Flavour;
-use crate::binary_serde::BinarySerde;
-use crate::routers::spray_and_wait::message::Message;
 
+/// Only the source sprays: every relay is handed a single copy and goes straight to waiting.
 #[derive(Clone)]
-pub struct Source {
-    messages_to_spray: Vec<MessageToSpray>,
-    messages_to_forward: Vec<(MessageId, NodeId)>
-}
-
-struct MessageToSpray {
-    id: MessageId,
-    copies_remaining: u16,
-    sprayed_node_ids: Vec<NodeId>
-}
-
-#[derive(Serialize, Deserialize, Hash, Clone)]
-pub struct PingPacket {
-    source_id: NodeId,
-}
-
-#[derive(Serialize, Deserialize, Hash, Clone)]
-pub struct FulfillmentPacket {
-    source_id: NodeId,
-    destination_id: NodeId,
-    messages: Vec<Message>,
-}
+pub struct Source;
 
 impl Flavour for Source {
-    type PingPacket = PingPacket;
-    type FulfillmentPacket = ();
-
-    fn new() -> Self {
-        todo!()
+    fn handed_copies(&self, _remaining_copies: u16) -> u16 {
+        1
     }
 }
-
-impl BinarySerde for PingPacket {}
-impl BinarySerde for FulfillmentPacket {}
\ No newline at end of file
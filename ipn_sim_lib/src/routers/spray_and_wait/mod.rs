@@ -1,5 +1,3 @@
 pub mod spray_and_wait;
 pub mod flavour;
-pub mod packet;
 pub mod flavours;
-pub mod message;
\ No newline at end of file
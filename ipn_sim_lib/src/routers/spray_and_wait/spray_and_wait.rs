@@ -1,66 +1,72 @@
-use crate::router::Router;
-use crate::utils::{NodeId, Data, TimeMetric, MessageId};
+use crate::message_destination::IsIncluded;
+use crate::ipn_sim::groups::Groups;
+use crate::router::components::ack_vector::Ack;
+use crate::router::components::bloom_filter::BloomParams;
+use crate::router::layered::forwarding_strategy::ForwardingStrategy;
+use crate::router::layered::layered_router::{LayeredRouter, Layers};
+use crate::router::layered::message::Message;
 use crate::routers::spray_and_wait::flavour::Flavour;
-use crate::router_link::RouterLink;
-use crate::events::create_message_event::MessageDestination;
-use bit_vec::BitBlock;
-use crate::routers::spray_and_wait::message::Message;
-use crate::message_buffer::MessageHandle;
-use crate::binary_serde::BinarySerde;
+use crate::routers::spray_and_wait::flavours::binary::Binary;
+use crate::routers::spray_and_wait::flavours::source::Source;
+use crate::utils::NodeId;
+
+/// Summary vector spray and wait: each message starts with `initial_copies` copies which are
+/// divided among relays according to the flavour. A node holding a single copy waits until it
+/// meets a destination.
+pub type SprayAndWait<F> = LayeredRouter<Spray<F>>;
+
+pub type BinarySprayAndWait = SprayAndWait<Binary>;
+
+pub type SourceSprayAndWait = SprayAndWait<Source>;
 
 #[derive(Clone)]
-pub struct SprayAndWait<F: Flavour> {
-    pub node_id: Option<NodeId>,
-    next_message_index: u16,
-    messages_to_forward: Vec<MessageToForward>,
-    flavour: F
+pub struct Spray<F: Flavour> {
+    pub initial_copies: u16,
+    pub flavour: F,
 }
 
-pub struct MessageToForward {
-    handle: MessageHandle,
-    remaining_destinations: MessageDestination,
-    remaining_copies: u32,
-}
+impl<F: Flavour> ForwardingStrategy for Spray<F> {
+    type CopyState = u16;
+
+    fn on_message_created(&mut self, _message: &Message) -> u16 {
+        self.initial_copies
+    }
 
-impl<F: Flavour> SprayAndWait<F> {
-    pub fn new() -> Self {
-        Self {
-            node_id: None,
-            next_message_index: 0,
-            messages_to_forward: vec![],
-            flavour: F::new(),
+    fn on_forward(&mut self, message: &Message, remaining_copies: &mut u16, neighbour_id: NodeId, groups: &Groups) -> Option<u16> {
+        if message.destination.is_included(&neighbour_id, groups) {
+            Some(1)
+        } else if *remaining_copies > 1 {
+            let handed_copies = self.flavour.handed_copies(*remaining_copies);
+            *remaining_copies -= handed_copies;
+            Some(handed_copies)
+        } else {
+            None
         }
     }
 }
 
-impl<F: Flavour> Router for SprayAndWait<F> {
-    fn on_init(&mut self, _link: &mut RouterLink, id: NodeId) {
-        self.node_id = Some(id);
+impl<F: Flavour> LayeredRouter<Spray<F>> {
+    pub fn new(flavour: F, summary_vector: impl Into<BloomParams>, initial_copies: u16) -> Self {
+        Self::with_strategy(Spray { initial_copies, flavour }, Layers {
+            summary_vector: summary_vector.into(),
+            beacon_interval: 1_000_000_000 * 60 * 15,
+            ping_on_receive: false,
+            ack: Ack::Bilateral,
+            bilateral_fulfillment: false,
+        })
     }
+}
 
-    fn on_message_created(&mut self, link: &mut RouterLink, destination: MessageDestination, payload: Data, ttl: Option<TimeMetric>) {
-        let message_id = (self.node_id.unwrap() as MessageId)
-            .overflowing_shl((NodeId::bytes() as u32) * 8)
-            .0 | self.next_message_index as MessageId;
-        self.next_message_index += 1;
-        link.report_message_created(message_id, destination.clone(), ttl);
-        let message = Message {
-            id: message_id,
-            source_id: self.node_id.unwrap(),
-            destination,
-            payload,
-            ttl,
-        };
-        if let Some(message_handle) = link.add_to_message_buffer(message.as_data()) {
-            self.messages_to_forward.push(MessageToForward {
-                handle: message_handle,
-                remaining_destinations: destination.clone(),
-                remaining_copies: 50
-            })
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use crate::routers::spray_and_wait::flavour::Flavour;
+    use crate::routers::spray_and_wait::flavours::binary::Binary;
+    use crate::routers::spray_and_wait::flavours::source::Source;
 
-    // fn on_data_received(&mut self, link: &mut RouterLink, data: Data) {
-    //     todo!()
-    // }
-}
\ No newline at end of file
+    #[test]
+    fn test_handed_copies() {
+        assert_eq!(Binary.handed_copies(50), 25);
+        assert_eq!(Binary.handed_copies(3), 1);
+        assert_eq!(Source.handed_copies(50), 1);
+    }
+}
@@ -0,0 +1,216 @@
+use std::f64::consts::PI;
+
+use crate::body::{Body, BodyRotation};
+use crate::ipn_sim::epoch::Epoch;
+use crate::movements::orbital_elements::OrbitalElements;
+use crate::movements::orbital_movement::OrbitalMovement;
+use crate::utils::{Shared, SpaceMetric};
+
+pub const ASTRONOMICAL_UNIT: SpaceMetric = 1.495_978_707e11;
+const DEG: SpaceMetric = PI / 180.;
+const KM: SpaceMetric = 1000.;
+
+/// Physical constants of a catalogued body, its rotation and its approximate mean orbit around
+/// `parent` at J2000, relative to the ecliptic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CatalogueBody {
+    pub name: &'static str,
+    pub parent: Option<&'static str>,
+    pub mass: SpaceMetric,
+    /// Equatorial radius.
+    pub radius: SpaceMetric,
+    pub flattening: SpaceMetric,
+    pub elements: OrbitalElements,
+    pub rotation: BodyRotation,
+}
+
+impl CatalogueBody {
+    /// Keplerian movement around the parent body, phased for a simulation starting at `epoch`.
+    pub fn movement_around(&self, parent: &Shared<Body>, epoch: Epoch) -> OrbitalMovement {
+        let period = self.elements.period(parent.borrow().mass);
+        let elapsed = (epoch.julian_date - Epoch::J2000.julian_date) * 86_400e9;
+        let mut elements = self.elements;
+        elements.mean_anomaly_at_epoch += (elapsed / period % 1.) * 2. * PI;
+        OrbitalMovement::elliptical(parent, elements, false)
+    }
+
+    /// Rotation phased for a simulation starting at `epoch`.
+    pub fn rotation_at(&self, epoch: Epoch) -> BodyRotation {
+        let elapsed = (epoch.julian_date - Epoch::J2000.julian_date) * 86_400e9;
+        let mut rotation = self.rotation;
+        rotation.meridian_at_epoch += (elapsed / rotation.period % 1.) * 2. * PI;
+        rotation
+    }
+
+    /// Sidereal rotation period in hours, negative if retrograde, with the axial tilt and the
+    /// prime meridian at J2000 in degrees.
+    const fn rotating(self, period: SpaceMetric, axial_tilt: SpaceMetric, meridian: SpaceMetric) -> Self {
+        CatalogueBody {
+            rotation: BodyRotation {
+                period: period * 3600e9,
+                axial_tilt: axial_tilt * DEG,
+                meridian_at_epoch: meridian * DEG,
+            },
+            ..self
+        }
+    }
+
+    /// Polar flattening of an oblate body.
+    const fn oblate(self, flattening: SpaceMetric) -> Self {
+        CatalogueBody {
+            flattening,
+            ..self
+        }
+    }
+}
+
+/// Planet elements are the JPL approximate mean elements. Moons of planets other than Earth have
+/// approximate ecliptic inclinations and an arbitrary phase.
+pub const CATALOGUE: &[CatalogueBody] = &[
+    body("Sun", None, 1.98847e30, 6.957e8, 0., 0., 0., 0., 0., 0.)
+        .rotating(609.12, 7.25, 84.176),
+    planet("Mercury", 3.3011e23, 2.4397e6, 0.38709927, 0.20563593, 7.00497902, 48.33076593, 77.45779628, 252.25032350)
+        .rotating(1407.6, 0.034, 329.548),
+    planet("Venus", 4.8675e24, 6.0518e6, 0.72333566, 0.00677672, 3.39467605, 76.67984255, 131.60246718, 181.97909950)
+        .rotating(-5832.6, 2.64, 160.20),
+    planet("Earth", 5.97217e24, 6.3781e6, 1.00000261, 0.01671123, 0., 0., 102.93768193, 100.46457166)
+        .rotating(23.9344696, 23.439281, 280.46061837)
+        .oblate(0.0033528),
+    body("Moon", Some("Earth"), 7.342e22, 1.7374e6, 384_400. * KM, 0.0549, 5.145, 125.08, 318.15, 135.27)
+        .rotating(655.728, 1.5424, 38.3213),
+    planet("Mars", 6.4171e23, 3.3962e6, 1.52371034, 0.09339410, 1.84969142, 49.55953891, -23.94362959, -4.55343205)
+        .rotating(24.6229, 25.19, 176.630)
+        .oblate(0.00589),
+    body("Phobos", Some("Mars"), 1.0659e16, 11.2667e3, 9_376. * KM, 0.0151, 26.04, 82.5, 0., 0.)
+        .rotating(7.6538, 26.04, 35.06),
+    body("Deimos", Some("Mars"), 1.4762e15, 6.2e3, 23_463.2 * KM, 0.00033, 27.58, 82.5, 0., 180.)
+        .rotating(30.2985, 27.58, 79.41),
+    planet("Jupiter", 1.89819e27, 7.1492e7, 5.20288700, 0.04838624, 1.30439695, 100.47390909, 14.72847983, 34.39644051)
+        .rotating(9.925, 3.13, 284.95)
+        .oblate(0.06487),
+    body("Io", Some("Jupiter"), 8.9319e22, 1.8216e6, 421_700. * KM, 0.0041, 2.2, 336.2, 0., 0.)
+        .rotating(42.4593, 2.2, 200.39),
+    body("Europa", Some("Jupiter"), 4.7998e22, 1.5608e6, 671_034. * KM, 0.009, 2.2, 336.2, 0., 90.)
+        .rotating(85.2283, 2.2, 36.022),
+    body("Ganymede", Some("Jupiter"), 1.4819e23, 2.6341e6, 1_070_412. * KM, 0.0013, 2.2, 336.2, 0., 180.)
+        .rotating(171.7093, 2.2, 44.064),
+    body("Callisto", Some("Jupiter"), 1.0759e23, 2.4103e6, 1_882_709. * KM, 0.0074, 2.2, 336.2, 0., 270.)
+        .rotating(400.5364, 2.2, 259.51),
+    planet("Saturn", 5.6834e26, 6.0268e7, 9.53667594, 0.05386179, 2.48599187, 113.66242448, 92.59887831, 49.95424423)
+        .rotating(10.656, 26.73, 38.90)
+        .oblate(0.09796),
+    body("Titan", Some("Saturn"), 1.3452e23, 2.5747e6, 1_221_870. * KM, 0.0288, 27.7, 169.5, 0., 0.)
+        .rotating(382.68, 27.7, 186.5872),
+    planet("Uranus", 8.6810e25, 2.5559e7, 19.18916464, 0.04725744, 0.77263783, 74.01692503, 170.95427630, 313.23810451)
+        .rotating(-17.24, 82.23, 203.81)
+        .oblate(0.0229),
+    planet("Neptune", 1.02413e26, 2.4764e7, 30.06992276, 0.00859048, 1.77004347, 131.78422574, 44.96476227, -55.12002969)
+        .rotating(16.11, 28.32, 249.978)
+        .oblate(0.0171),
+    body("Triton", Some("Neptune"), 2.139e22, 1.3534e6, 354_759. * KM, 0.000016, 130.6, 177.6, 0., 0.)
+        .rotating(141.0445, 130.6, 296.53),
+];
+
+pub fn get(name: &str) -> Option<&'static CatalogueBody> {
+    CATALOGUE.iter().find(|body| body.name == name)
+}
+
+/// Angles in degrees.
+#[allow(clippy::too_many_arguments)]
+const fn body(
+    name: &'static str,
+    parent: Option<&'static str>,
+    mass: SpaceMetric,
+    radius: SpaceMetric,
+    semimajor_axis: SpaceMetric,
+    eccentricity: SpaceMetric,
+    inclination: SpaceMetric,
+    ascending_node: SpaceMetric,
+    argument_of_periapsis: SpaceMetric,
+    mean_anomaly: SpaceMetric,
+) -> CatalogueBody {
+    CatalogueBody {
+        name,
+        parent,
+        mass,
+        radius,
+        elements: OrbitalElements {
+            semimajor_axis,
+            eccentricity,
+            inclination: inclination * DEG,
+            ascending_node: ascending_node * DEG,
+            argument_of_periapsis: argument_of_periapsis * DEG,
+            mean_anomaly_at_epoch: mean_anomaly * DEG,
+        },
+        flattening: 0.,
+        rotation: BodyRotation {
+            period: 0.,
+            axial_tilt: 0.,
+            meridian_at_epoch: 0.,
+        },
+    }
+}
+
+/// A planet from its semi-major axis in astronomical units and its longitudes of the ascending
+/// node, perihelion and mean longitude in degrees.
+#[allow(clippy::too_many_arguments)]
+const fn planet(
+    name: &'static str,
+    mass: SpaceMetric,
+    radius: SpaceMetric,
+    semimajor_axis: SpaceMetric,
+    eccentricity: SpaceMetric,
+    inclination: SpaceMetric,
+    ascending_node: SpaceMetric,
+    perihelion: SpaceMetric,
+    mean_longitude: SpaceMetric,
+) -> CatalogueBody {
+    body(
+        name,
+        Some("Sun"),
+        mass,
+        radius,
+        semimajor_axis * ASTRONOMICAL_UNIT,
+        eccentricity,
+        inclination,
+        ascending_node,
+        perihelion - ascending_node,
+        mean_longitude - perihelion,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::MetricSpace;
+
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::solar_system::ASTRONOMICAL_UNIT;
+
+    #[test]
+    fn test_catalogue_bodies() {
+        let builder = IpnSimBuilder::new(0).add_catalogue_bodies(&["Sun", "Earth", "Moon", "Mars"]);
+        let position = |name: &str| builder.get_body_by_name(name).borrow().position;
+
+        let earth_distance = position("Earth").distance(position("Sun"));
+        assert!((earth_distance / ASTRONOMICAL_UNIT - 1.).abs() < 0.02);
+        let moon_distance = position("Moon").distance(position("Earth"));
+        assert!((moon_distance / 384_400e3 - 1.).abs() < 0.06);
+        let mars_distance = position("Mars").distance(position("Sun")) / ASTRONOMICAL_UNIT;
+        assert!((1.38..1.67).contains(&mars_distance));
+
+        let period = crate::solar_system::get("Earth").unwrap().elements.period(1.98847e30);
+        assert!((period / 86_400e9 - 365.25).abs() < 0.5);
+
+        assert_eq!(builder.get_body_by_name("Mars").borrow().flattening, 0.00589);
+        assert!((builder.get_body_by_name("Earth").borrow().polar_radius() - 6.3568e6).abs() < 1e3);
+
+        let moon = IpnSimBuilder::new(0).add_catalogue_bodies(&["Earth", "Moon"]).get_body(1);
+        assert_eq!(moon.borrow().name, "Moon");
+    }
+
+    #[test]
+    #[should_panic(expected = "Pluto is not in the solar system catalogue")]
+    fn test_unknown_catalogue_body() {
+        IpnSimBuilder::new(0).add_catalogue_bodies(&["Sun", "Pluto"]);
+    }
+}
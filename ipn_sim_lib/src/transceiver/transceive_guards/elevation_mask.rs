@@ -0,0 +1,93 @@
+use cgmath::{InnerSpace, MetricSpace};
+
+use crate::movements::surface_movement::SurfaceMovement;
+use crate::node::node::Node;
+use crate::transceiver::transceive_guard::TransceiveGuard;
+use crate::utils::SpaceMetric;
+
+/// Limits a node on the surface of a body to nodes above `minimum_elevation`, in radians above
+/// its local horizon, and within `transceive_distance`. Nodes not moving with a
+/// [`SurfaceMovement`] have no horizon and are only limited by distance.
+#[derive(Clone)]
+pub struct ElevationMaskGuard {
+    minimum_elevation: SpaceMetric,
+    transceive_distance: SpaceMetric,
+}
+
+impl ElevationMaskGuard {
+    pub fn new(minimum_elevation: SpaceMetric, transceive_distance: SpaceMetric) -> Self {
+        Self {
+            minimum_elevation,
+            transceive_distance,
+        }
+    }
+
+    /// Elevation of `other_node` above the horizon of `node`, or `None` if `node` is not on a
+    /// surface.
+    pub fn elevation(node: &Node, other_node: &Node) -> Option<SpaceMetric> {
+        let surface_movement = node.movement.downcast_ref::<SurfaceMovement>()?;
+        let up = (node.position - surface_movement.body().borrow().position).normalize();
+        let direction = (other_node.position - node.position).normalize();
+        Some(up.dot(direction).clamp(-1., 1.).asin())
+    }
+}
+
+impl TransceiveGuard for ElevationMaskGuard {
+    fn can_transceive(&self, transmitting_node: &Node, receiving_node: &Node) -> bool {
+        transmitting_node.position.distance2(receiving_node.position) <= self.transceive_distance.powi(2)
+            && Self::elevation(transmitting_node, receiving_node)
+                .is_none_or(|elevation| elevation >= self.minimum_elevation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{EuclideanSpace, Point3};
+
+    use crate::ipn_sim::ipn_sim_builder::IpnSimBuilder;
+    use crate::movements::static_movement::StaticMovement;
+    use crate::movements::surface_movement::SurfaceMovement;
+    use crate::node::node_builder::NodeBuilder;
+    use crate::routers::test_router::TestRouter;
+    use crate::transceiver::transceive_guard::TransceiveGuard;
+    use crate::transceiver::transceive_guards::elevation_mask::ElevationMaskGuard;
+
+    #[test]
+    fn test_elevation_mask() {
+        let mut builder = IpnSimBuilder::new(0).add_body("Earth", 5.972e24, StaticMovement::new(Point3::origin()), 6.371e6);
+        let station = SurfaceMovement::new(&builder.get_body(0), 0., 0., 0.);
+        let guard = ElevationMaskGuard::new(10f64.to_radians(), 1e8);
+
+        builder = builder.add_node(NodeBuilder::new()
+            .name("station")
+            .movement(station)
+            .message_buffer_size(1)
+            .router(TestRouter)
+            .transceive_speed(1.)
+            .transceive_guard(guard.clone()));
+        // Spacecraft 45, 5 and -5 degrees above the station's horizon, and one out of range.
+        for &position in &[
+            Point3::new(6.371e6 + 1e6, 1e6, 0.),
+            Point3::new(6.371e6 + 0.0875e6, 1e6, 0.),
+            Point3::new(6.371e6 - 0.0875e6, 1e6, 0.),
+            Point3::new(2e8, 0., 0.),
+        ] {
+            builder = builder.add_node(NodeBuilder::new()
+                .name("spacecraft")
+                .movement(StaticMovement::new(position))
+                .message_buffer_size(1)
+                .router(TestRouter)
+                .transceive_speed(1.)
+                .transceive_guard(guard.clone()));
+        }
+
+        let sim = builder.build();
+        let station = sim.get_node(0);
+        let expected = [true, false, false, false];
+        for (index, &can_transceive) in expected.iter().enumerate() {
+            let spacecraft = sim.get_node(index as u16 + 1);
+            assert_eq!(guard.can_transceive(&station.borrow(), &spacecraft.borrow()), can_transceive);
+            assert_eq!(guard.can_transceive(&spacecraft.borrow(), &station.borrow()), index < 3);
+        }
+    }
+}
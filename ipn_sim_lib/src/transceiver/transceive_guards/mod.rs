@@ -1 +1,2 @@
+pub mod elevation_mask;
 pub mod simple;
@@ -1,14 +1,25 @@
 use std::collections::VecDeque;
 
 use crate::transceiver::transceive_guard::TransceiveGuard;
-use crate::utils::{Data, TimeMetric};
+use crate::packet::PacketData;
+use crate::utils::TimeMetric;
 
 pub struct Transceiver {
     pub transmit_speed: f64,
 
+    /// Fixed link layer framing overhead added to every transmitted packet.
+    pub frame_header_size: usize,
+
+    /// Largest frame the link carries, framing included. The LTP convergence layer segments
+    /// blocks to fit, other packets are sent whole.
+    pub max_frame_size: usize,
+
+    /// Probability that a transmitted packet is lost before reaching each receiver.
+    pub loss_probability: f64,
+
     pub busy_until: TimeMetric,
 
-    buffer: VecDeque<Data>,
+    buffer: VecDeque<PacketData>,
 
     pub guard: Box<dyn TransceiveGuard>,
 }
@@ -17,25 +28,28 @@ impl Transceiver {
     pub fn new(transmit_speed: f64, guard: Box<dyn TransceiveGuard>) -> Self {
         Self {
             transmit_speed,
+            frame_header_size: 0,
+            max_frame_size: 1500,
+            loss_probability: 0.,
             busy_until: 0,
             buffer: VecDeque::new(),
             guard,
         }
     }
 
-    pub fn add_to_buffer(&mut self, data: Data, current_time: TimeMetric) -> TimeMetric {
-        let transmit_time = self.get_transmit_time(&data);
-        self.buffer.push_back(data);
+    pub fn add_to_buffer(&mut self, packet: PacketData, current_time: TimeMetric) -> TimeMetric {
+        let transmit_time = self.get_transmit_time(&packet);
+        self.buffer.push_back(packet);
         let transmit_start = current_time.max(self.busy_until);
         self.busy_until = transmit_start + transmit_time;
         transmit_start
     }
 
-    pub fn pop_head_data(&mut self) -> Data {
+    pub fn pop_head_packet(&mut self) -> PacketData {
         self.buffer.pop_front().unwrap()
     }
 
-    pub fn get_transmit_time(&self, data: &Data) -> TimeMetric {
-        (data.len() as f64 / self.transmit_speed) as TimeMetric
+    pub fn get_transmit_time(&self, packet: &PacketData) -> TimeMetric {
+        ((self.frame_header_size + packet.size.total()) as f64 / self.transmit_speed) as TimeMetric
     }
 }
@@ -11,6 +11,7 @@ pub type Data = Box<[u8]>;
 
 pub type NodeId = u16;
 pub type MessageId = u32;
+pub type GroupId = u16;
 
 pub const C: SpaceMetric = 299_792_458e-9;
 pub const G: SpaceMetric = 6.674e-11;
@@ -27,6 +28,20 @@ pub fn log(s: &str) {
     println!("{}", s);
 }
 
+/// Id of the `index`th message created by a node: the node id in the upper half and the index in
+/// the lower half.
+pub fn message_id(node_id: NodeId, index: u16) -> MessageId {
+    (node_id as MessageId) << 16 | index as MessageId
+}
+
+/// Allocates the next id of a message created by a node. Indices wrap, so a node's ids repeat after
+/// 65,536 messages.
+pub fn next_message_id(node_id: NodeId, next_message_index: &mut u16) -> MessageId {
+    let message_id = message_id(node_id, *next_message_index);
+    *next_message_index = next_message_index.wrapping_add(1);
+    message_id
+}
+
 pub type Shared<T> = Rc<RefCell<T>>;
 
 pub fn shared<T>(value: T) -> Shared<T> {
@@ -38,7 +53,7 @@ mod tests {
     use std::cell::RefCell;
     use std::rc::Rc;
 
-    use crate::utils::Shared;
+    use crate::utils::{next_message_id, Shared};
 
     trait X {}
 
@@ -58,4 +73,11 @@ mod tests {
 
         a(&b, &mut x);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_message_ids_wrap() {
+        let mut next_message_index = u16::MAX;
+        assert_eq!(next_message_id(3, &mut next_message_index), 3 << 16 | 0xffff);
+        assert_eq!(next_message_id(3, &mut next_message_index), 3 << 16);
+    }
+}
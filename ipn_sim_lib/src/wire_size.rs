@@ -0,0 +1,104 @@
+use std::mem::size_of;
+
+use bit_vec::BitVec;
+
+use crate::message_destination::MessageDestination;
+
+/// Bytes used to prefix variable length collections on the wire.
+pub const LENGTH_PREFIX_SIZE: usize = 2;
+
+/// Bytes used to tag enum variants and optional fields on the wire.
+pub const TAG_SIZE: usize = 1;
+
+/// The nominal number of bytes a value occupies when encoded by a realistic protocol, independent
+/// of its in-memory or serializer representation.
+pub trait WireSize {
+    fn wire_size(&self) -> usize;
+}
+
+macro_rules! impl_fixed_wire_size {
+    ($($t:ty),*) => {
+        $(
+            impl WireSize for $t {
+                fn wire_size(&self) -> usize {
+                    size_of::<$t>()
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_wire_size!(bool, u8, u16, u32, u64, f32, f64);
+
+impl WireSize for () {
+    fn wire_size(&self) -> usize {
+        0
+    }
+}
+
+impl WireSize for BitVec {
+    fn wire_size(&self) -> usize {
+        self.len().div_ceil(8)
+    }
+}
+
+impl WireSize for [u8] {
+    fn wire_size(&self) -> usize {
+        LENGTH_PREFIX_SIZE + self.len()
+    }
+}
+
+impl<T: WireSize + ?Sized> WireSize for Box<T> {
+    fn wire_size(&self) -> usize {
+        (**self).wire_size()
+    }
+}
+
+impl<T: WireSize> WireSize for Option<T> {
+    fn wire_size(&self) -> usize {
+        TAG_SIZE + self.as_ref().map_or(0, WireSize::wire_size)
+    }
+}
+
+impl<T: WireSize> WireSize for Vec<T> {
+    fn wire_size(&self) -> usize {
+        LENGTH_PREFIX_SIZE + self.iter().map(WireSize::wire_size).sum::<usize>()
+    }
+}
+
+impl<A: WireSize, B: WireSize> WireSize for (A, B) {
+    fn wire_size(&self) -> usize {
+        self.0.wire_size() + self.1.wire_size()
+    }
+}
+
+impl<T: WireSize> WireSize for MessageDestination<T> {
+    fn wire_size(&self) -> usize {
+        TAG_SIZE
+            + match self {
+                MessageDestination::All => 0,
+                MessageDestination::Single(id) => id.wire_size(),
+                MessageDestination::Multiple(ids) => ids.wire_size(),
+                MessageDestination::Multicast(group_id) | MessageDestination::Anycast(group_id) => group_id.wire_size(),
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bit_vec::BitVec;
+
+    use crate::message_destination::MessageDestination;
+    use crate::utils::NodeId;
+    use crate::wire_size::WireSize;
+
+    #[test]
+    fn test_wire_sizes() {
+        assert_eq!(BitVec::from_elem(1024, true).wire_size(), 128);
+        assert_eq!(BitVec::from_elem(9, false).wire_size(), 2);
+        assert_eq!(Some(0u32).wire_size(), 5);
+        assert_eq!(None::<u32>.wire_size(), 1);
+        assert_eq!(vec![0u16; 3].wire_size(), 8);
+        assert_eq!(MessageDestination::<NodeId>::Single(0).wire_size(), 3);
+    }
+}
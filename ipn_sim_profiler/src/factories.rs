@@ -3,12 +3,18 @@ use std::fs::{File, OpenOptions};
 
 use ipn_sim_lib::cgmath::{EuclideanSpace, Point3};
 use ipn_sim_lib::events::create_message_event::CreateMessageEvent;
+use ipn_sim_lib::ipn_sim::epoch::Epoch;
 use ipn_sim_lib::ipn_sim::ipn_sim_builder::IpnSimBuilder;
 use ipn_sim_lib::message_destination::MessageDestination;
 use ipn_sim_lib::movements::orbital_movement::OrbitalMovement;
 use ipn_sim_lib::movements::static_movement::StaticMovement;
+use ipn_sim_lib::movements::tle::Tle;
+use ipn_sim_lib::movements::tle_movement::TleMovement;
 use ipn_sim_lib::node::node_builder::NodeBuilder;
+use ipn_sim_lib::router::Router;
 use ipn_sim_lib::routers::{epidemic_2, epidemic_2::epidemic::Ack};
+use ipn_sim_lib::routers::direct_delivery::direct_delivery::DirectDelivery;
+use ipn_sim_lib::routers::first_contact::first_contact::FirstContact;
 use ipn_sim_lib::routers::epidemic::epidemic::Epidemic;
 // use ipn_sim_lib::routers::epidemic::flavours::ack::Ack;
 use ipn_sim_lib::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
@@ -85,6 +91,26 @@ pub fn orbiting_rings() -> IpnSimBuilder {
 }
 
 pub fn constellation() -> IpnSimBuilder {
+    constellation_with(|| epidemic_2::epidemic::Epidemic::new(
+        1024 * 8,
+        1_000_000_000 * 60 * 15,
+        false,
+        Ack::Bilateral,
+        false
+    ))
+}
+
+/// The constellation with the Direct Delivery lower bound baseline.
+pub fn direct_delivery_constellation() -> IpnSimBuilder {
+    constellation_with(|| DirectDelivery::new(1024 * 8, 1_000_000_000 * 60 * 15))
+}
+
+/// The constellation with the First Contact lower bound baseline.
+pub fn first_contact_constellation() -> IpnSimBuilder {
+    constellation_with(|| FirstContact::new(1024 * 8, 1_000_000_000 * 60 * 15))
+}
+
+fn constellation_with<R: Router + 'static>(router: impl Fn() -> R) -> IpnSimBuilder {
     let plane_count = 10;
     let plane_size = 10;
     let plane_radius = 550_000. + 6.371e6;
@@ -115,14 +141,7 @@ pub fn constellation() -> IpnSimBuilder {
                         ascending_node_offset_step * plane_index as SpaceMetric,
                         true,
                     )).message_buffer_size(1024)
-                    // .router(Epidemic::<Ack>::new(1024 * 8, 1_000_000_000 * 3600))
-                    .router(epidemic_2::epidemic::Epidemic::new(
-                        1024 * 8,
-                        1_000_000_000 * 60 * 15,
-                        false,
-                        Ack::Bilateral,
-                        false
-                    ))
+                    .router(router())
                     .transceive_speed(1.)
                     .transceive_guard(SimpleTransceiveGuard::new(
                         0.5 * plane_radius as SpaceMetric,
@@ -152,4 +171,64 @@ pub fn constellation() -> IpnSimBuilder {
     }
 
     builder
-}
\ No newline at end of file
+}
+
+/// Satellites from a local file of two line element sets, e.g. a Starlink or Iridium catalogue,
+/// starting at the most recent element set epoch. Panics if none of them can be propagated.
+pub fn tle_constellation(path: &str) -> IpnSimBuilder {
+    let tles = Tle::parse_all(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let epoch = tles
+        .iter()
+        .map(|tle| tle.epoch)
+        .fold(Epoch::J2000, |latest, epoch| if epoch.julian_date > latest.julian_date { epoch } else { latest });
+
+    let mut builder = IpnSimBuilder::new(1_000_000_000 * 3600 * 24)
+        .epoch(epoch)
+        .add_catalogue_bodies(&["Earth"]);
+
+    let earth = builder.get_body(0);
+    let mut node_count = 0;
+
+    for tle in tles {
+        let name = tle.name.clone().unwrap_or_else(|| tle.catalogue_number.to_string());
+        let movement = match TleMovement::new(&earth, tle, epoch) {
+            Ok(movement) => movement,
+            Err(_) => continue,
+        };
+        builder = builder.add_node(
+            NodeBuilder::new()
+                .name(name)
+                .movement(movement)
+                .message_buffer_size(1024)
+                .router(epidemic_2::epidemic::Epidemic::new(
+                    1024 * 8,
+                    1_000_000_000 * 60 * 15,
+                    false,
+                    Ack::Bilateral,
+                    false
+                ))
+                .transceive_speed(1.)
+                .transceive_guard(SimpleTransceiveGuard::new(2_000_000.))
+        );
+        node_count += 1;
+    }
+    assert!(node_count > 0, "no propagatable element sets in {}", path);
+
+    for _ in 0..400 {
+        let node = builder.get_node(rand::random::<usize>() % node_count);
+        let time = rand::random::<TimeMetric>() % 1_000_000_000 * 3600 * 6;
+        builder = builder.add_event(
+            time,
+            CreateMessageEvent {
+                node,
+                destination: MessageDestination::Single(
+                    rand::random::<NodeId>() % node_count as NodeId,
+                ),
+                payload: "Hello there, World!".as_bytes().to_vec().into_boxed_slice(),
+                ttl: Some(time + 1_000_000_000 * 3600 * 12),
+            },
+        )
+    }
+
+    builder
+}
@@ -1,3 +1,5 @@
+use std::env;
+
 use crate::profiler::Profiler;
 use crate::profiler_report::ProfilerReport;
 
@@ -6,8 +8,17 @@ pub mod profiler;
 pub mod profiler_report;
 
 fn main() {
-    Profiler::new()
+    let mut profiler = Profiler::new();
+    profiler
         .run_scenario("orbiting rings", factories::orbiting_rings, 5)
         .run_scenario("constellation", factories::constellation, 5)
-        .report();
+        .run_scenario("constellation, direct delivery", factories::direct_delivery_constellation, 5)
+        .run_scenario("constellation, first contact", factories::first_contact_constellation, 5);
+
+    // Element set catalogues are not distributed with the profiler, so this one is opt in.
+    if let Ok(path) = env::var("TLE_PATH") {
+        profiler.run_scenario("TLE constellation", || factories::tle_constellation(&path), 5);
+    }
+
+    profiler.report();
 }
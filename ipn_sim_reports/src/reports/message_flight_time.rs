@@ -7,10 +7,10 @@ use ipn_sim_lib::event::Event;
 use ipn_sim_lib::events::router_event::{RouterEvent, RouterEventType};
 use ipn_sim_lib::ipn_sim::ipn_sim::IpnSim;
 use ipn_sim_lib::report::Report;
-use ipn_sim_lib::utils::{MessageId, NodeId, Shared, TimeMetric};
+use ipn_sim_lib::utils::{MessageId, TimeMetric};
 
 use crate::graph_report::GraphReport;
-use crate::utils::{destination_to_ids, mean_std_dev};
+use crate::utils::{mean_std_dev, PendingDestinations};
 use crate::utils::format_time::format_time;
 use crate::utils::paths::render_mean_sd_graph;
 use crate::value_logger::ValueLogger;
@@ -19,6 +19,8 @@ use crate::time_series_report::TimeSeriesReport;
 #[derive(Clone)]
 pub struct MessageFlightTime {
     messages_in_flight: HashMap<MessageId, MessageInFlight>,
+    /// Group messages already completed, which further members may still deliver.
+    completed_group_message_ids: HashSet<MessageId>,
     message_flight_times: Vec<TimeMetric>,
     pub average_message_flight_times: ValueLogger<f32>,
     pub message_flight_time_std_devs: ValueLogger<f32>,
@@ -27,13 +29,14 @@ pub struct MessageFlightTime {
 #[derive(Clone)]
 struct MessageInFlight {
     sent_time: TimeMetric,
-    remaining_destination_ids: HashSet<NodeId>,
+    pending_destinations: PendingDestinations,
 }
 
 impl MessageFlightTime {
     pub fn new() -> Self {
         Self {
             messages_in_flight: HashMap::new(),
+            completed_group_message_ids: HashSet::new(),
             message_flight_times: vec![],
             average_message_flight_times: ValueLogger::new(0., true),
             message_flight_time_std_devs: ValueLogger::new(0., true),
@@ -49,14 +52,19 @@ impl Report for MessageFlightTime {
                     RouterEventType::MessageCreated { id, destination, .. } => {
                         self.messages_in_flight.insert(*id, MessageInFlight {
                             sent_time: sim.time,
-                            remaining_destination_ids: destination_to_ids(destination, sim),
+                            pending_destinations: PendingDestinations::new(destination, sim),
                         });
                     }
                     RouterEventType::MessageDelivered { id, .. } => {
+                        if self.completed_group_message_ids.contains(id) {
+                            continue;
+                        }
                         let message_in_flight = self.messages_in_flight.get_mut(id).unwrap();
 
-                        message_in_flight.remaining_destination_ids.remove(&router_event.node.borrow().id);
-                        if message_in_flight.remaining_destination_ids.is_empty() {
+                        if message_in_flight.pending_destinations.deliver(router_event.node.borrow().id, sim) {
+                            if message_in_flight.pending_destinations.is_group() {
+                                self.completed_group_message_ids.insert(*id);
+                            }
                             self.message_flight_times.push(sim.time - message_in_flight.sent_time);
 
                             let (average_message_flight_time, message_flight_time_std_dev) =
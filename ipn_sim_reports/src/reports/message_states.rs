@@ -1,5 +1,4 @@
 use std::collections::{HashMap, HashSet};
-use std::iter;
 use std::rc::Rc;
 
 use upcast::Upcast;
@@ -9,18 +8,19 @@ use ipn_sim_lib::event::Event;
 use ipn_sim_lib::events::router_event::{RouterEvent, RouterEventType};
 use ipn_sim_lib::ipn_sim::ipn_sim::IpnSim;
 use ipn_sim_lib::report::Report;
-use ipn_sim_lib::utils::{MessageId, NodeId, Shared, TimeMetric};
+use ipn_sim_lib::utils::{MessageId, TimeMetric};
 
 use crate::graph_report::GraphReport;
+use crate::utils::PendingDestinations;
 use crate::utils::paths::value_path;
 use crate::value_logger::ValueLogger;
 use crate::time_series_report::TimeSeriesReport;
-use ipn_sim_lib::message_destination::MessageDestination;
-use ipn_sim_lib::node::node::Node;
 
 #[derive(Clone)]
 pub struct MessageStates {
     messages_in_flight: HashMap<MessageId, MessageInFlight>,
+    /// Group messages already completed, which further members may still deliver.
+    completed_group_message_ids: HashSet<MessageId>,
     pub created_message_counts: ValueLogger<u16>,
     pub delivered_message_counts: ValueLogger<u16>,
     pub dropped_message_counts: ValueLogger<u16>,
@@ -30,7 +30,7 @@ pub struct MessageStates {
 struct MessageInFlight {
     sent_time: TimeMetric,
     alive: bool,
-    remaining_destination_ids: HashSet<NodeId>,
+    pending_destinations: PendingDestinations,
     ttl: Option<TimeMetric>,
 }
 
@@ -38,6 +38,7 @@ impl MessageStates {
     pub fn new() -> Self {
         Self {
             messages_in_flight: HashMap::new(),
+            completed_group_message_ids: HashSet::new(),
             created_message_counts: ValueLogger::new(0, true),
             delivered_message_counts: ValueLogger::new(0, true),
             dropped_message_counts: ValueLogger::new(0, true),
@@ -79,23 +80,21 @@ impl Report for MessageStates {
                             MessageInFlight {
                                 sent_time: sim.time,
                                 alive: true,
-                                remaining_destination_ids: match destination {
-                                    MessageDestination::<Shared<Node>>::All =>
-                                        sim.nodes.as_ref().unwrap().iter().map(|node| node.borrow().id).collect(),
-                                    MessageDestination::<Shared<Node>>::Single(node) =>
-                                        iter::once(node.borrow().id).collect(),
-                                    MessageDestination::<Shared<Node>>::Multiple(nodes) =>
-                                        nodes.iter().map(|node| node.borrow().id).collect()
-                                },
+                                pending_destinations: PendingDestinations::new(destination, sim),
                                 ttl: *ttl,
                             },
                         );
                     }
                     RouterEventType::MessageDelivered { id, .. } => {
+                        if self.completed_group_message_ids.contains(id) {
+                            continue;
+                        }
                         let message_in_flight = self.messages_in_flight.get_mut(id).unwrap();
 
-                        message_in_flight.remaining_destination_ids.remove(&router_event.node.borrow().id);
-                        if message_in_flight.remaining_destination_ids.is_empty() {
+                        if message_in_flight.pending_destinations.deliver(router_event.node.borrow().id, sim) {
+                            if message_in_flight.pending_destinations.is_group() {
+                                self.completed_group_message_ids.insert(*id);
+                            }
                             if message_in_flight.alive {
                                 // self.delivered_message_counts.log_value(sim.time, self.delivered_message_counts.value);
                                 self.delivered_message_counts.log_value(sim.time, self.delivered_message_counts.value + 1);
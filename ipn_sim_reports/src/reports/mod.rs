@@ -4,4 +4,6 @@ pub mod send_deliver_ratio;
 pub mod message_flight_time;
 pub mod node_positions;
 pub mod messages;
-pub mod single_message_graph;
\ No newline at end of file
+pub mod single_message_graph;
+pub mod packet_bytes;
+pub mod summary_vector_false_positives;
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use ipn_sim_lib::event::Event;
+use ipn_sim_lib::events::transmit_end_event::TransmitEndEvent;
+use ipn_sim_lib::ipn_sim::ipn_sim::IpnSim;
+use ipn_sim_lib::report::Report;
+
+pub const UNTYPED_PACKET_TYPE: &str = "Untyped";
+
+#[derive(Default)]
+pub struct PacketBytes {
+    pub packet_types: HashMap<&'static str, PacketTypeBytes>,
+}
+
+#[derive(Default)]
+pub struct PacketTypeBytes {
+    pub packets_sent: usize,
+    pub header_bytes_sent: usize,
+    pub payload_bytes_sent: usize,
+}
+
+impl PacketTypeBytes {
+    pub fn bytes_sent(&self) -> usize {
+        self.header_bytes_sent + self.payload_bytes_sent
+    }
+}
+
+impl PacketBytes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn total_bytes_sent(&self) -> usize {
+        self.packet_types
+            .values()
+            .map(PacketTypeBytes::bytes_sent)
+            .sum()
+    }
+}
+
+impl Report for PacketBytes {
+    fn on_tick(&mut self, _sim: &IpnSim, events: &Vec<Box<dyn Event>>) {
+        for event in events {
+            if let Some(transmit_end_event) = event.downcast_ref::<TransmitEndEvent>() {
+                let packet = &transmit_end_event.packet;
+                let packet_type_bytes = self.packet_types
+                    .entry(packet.packet_type.unwrap_or(UNTYPED_PACKET_TYPE))
+                    .or_default();
+                packet_type_bytes.packets_sent += 1;
+                packet_type_bytes.header_bytes_sent += packet.size.header;
+                packet_type_bytes.payload_bytes_sent += packet.size.payload;
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use yew::prelude::*;
+
+use ipn_sim_lib::event::Event;
+use ipn_sim_lib::events::router_event::{RouterEvent, RouterEventType};
+use ipn_sim_lib::ipn_sim::ipn_sim::IpnSim;
+use ipn_sim_lib::report::Report;
+use ipn_sim_lib::utils::{MessageId, NodeId, TimeMetric};
+
+use crate::graph_report::GraphReport;
+use crate::time_series_report::TimeSeriesReport;
+use crate::utils::paths::time_series_path;
+use crate::value_logger::ValueLogger;
+
+/// Counts transfers suppressed because a neighbour's summary vector claimed a message the
+/// neighbour had never seen. Each message and neighbour pair is counted once, both among the
+/// suppressions and among the false positives.
+#[derive(Clone)]
+pub struct SummaryVectorFalsePositives {
+    pub false_positives: ValueLogger<u16>,
+    seen: HashMap<NodeId, HashSet<MessageId>>,
+    suppressed: HashSet<(MessageId, NodeId)>,
+    counted: HashSet<(MessageId, NodeId)>,
+}
+
+impl SummaryVectorFalsePositives {
+    pub fn new() -> Self {
+        Self {
+            false_positives: ValueLogger::new(0, true),
+            seen: HashMap::new(),
+            suppressed: HashSet::new(),
+            counted: HashSet::new(),
+        }
+    }
+
+    /// Share of suppressed message and neighbour pairs that were false positives.
+    pub fn false_positive_ratio(&self) -> f32 {
+        if self.suppressed.is_empty() {
+            0.
+        } else {
+            self.counted.len() as f32 / self.suppressed.len() as f32
+        }
+    }
+}
+
+impl Report for SummaryVectorFalsePositives {
+    fn on_tick(&mut self, sim: &IpnSim, events: &Vec<Box<dyn Event>>) {
+        let mut false_positives_changed = false;
+        for event in events {
+            if let Some(router_event) = event.downcast_ref::<RouterEvent>() {
+                let node_id = router_event.node.borrow().id;
+                match &router_event.event_type {
+                    RouterEventType::MessageCreated { id, .. }
+                    | RouterEventType::MessageReceived { id, .. }
+                    | RouterEventType::MessageDelivered { id, .. } => {
+                        self.seen.entry(node_id).or_default().insert(*id);
+                    }
+                    RouterEventType::MessageSuppressed { id, neighbour_node } => {
+                        let neighbour_id = neighbour_node.borrow().id;
+                        if !self.suppressed.insert((*id, neighbour_id)) {
+                            continue;
+                        }
+
+                        let has_seen = self.seen
+                            .get(&neighbour_id)
+                            .map_or(false, |seen| seen.contains(id));
+
+                        if !has_seen {
+                            self.counted.insert((*id, neighbour_id));
+                            false_positives_changed = true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if false_positives_changed {
+            let false_positive_count = self.counted.len().min(u16::MAX as usize) as u16;
+            self.false_positives.log_value(sim.time, false_positive_count);
+        }
+    }
+}
+
+impl TimeSeriesReport for SummaryVectorFalsePositives {
+    fn render_body(
+        &self,
+        scale_x: &dyn Fn(f32) -> f32,
+        scale_y: &dyn Fn(f32) -> f32,
+        domain_width: f32,
+        domain_height: f32,
+    ) -> Html {
+        let mut path = time_series_path(
+            self.false_positives.history.iter(),
+            scale_x,
+            scale_y,
+        );
+        path = format!("M 0 {} {} H {}", domain_height, path, domain_width);
+
+        html! {
+            <path
+                fill="none"
+                stroke="black"
+                d=path
+            ></path>
+        }
+    }
+
+    fn y_max_value(&self) -> f32 {
+        self.false_positives.max_value() as f32
+    }
+}
+
+impl GraphReport for SummaryVectorFalsePositives {
+    fn render_graph(&self, width: u16, height: u16, sim_time: TimeMetric) -> Html {
+        TimeSeriesReport::render_graph(self, width, height, sim_time)
+    }
+}
@@ -1,8 +1,9 @@
+use std::collections::HashSet;
 use std::iter;
 use std::iter::FromIterator;
 
 use ipn_sim_lib::ipn_sim::ipn_sim::IpnSim;
-use ipn_sim_lib::utils::{NodeId, Shared};
+use ipn_sim_lib::utils::{GroupId, NodeId, Shared};
 use ipn_sim_lib::message_destination::MessageDestination;
 use ipn_sim_lib::node::node::Node;
 
@@ -27,6 +28,57 @@ pub fn destination_to_ids<T: FromIterator<NodeId>>(destination: &MessageDestinat
         MessageDestination::<Shared<Node>>::Single(node) =>
             iter::once(node.borrow().id).collect(),
         MessageDestination::<Shared<Node>>::Multiple(nodes) =>
-            nodes.iter().map(|node| node.borrow().id).collect()
+            nodes.iter().map(|node| node.borrow().id).collect(),
+        MessageDestination::<Shared<Node>>::Multicast(group_id) | MessageDestination::<Shared<Node>>::Anycast(group_id) =>
+            sim.groups.members(*group_id).into_iter().collect(),
+    }
+}
+
+/// The destinations a message has yet to reach.
+#[derive(Clone)]
+pub enum PendingDestinations {
+    Nodes(HashSet<NodeId>),
+    /// Members are looked up on each delivery, so nodes joining the group while the message is in
+    /// flight are waited for as well.
+    Multicast {
+        group_id: GroupId,
+        delivered_ids: HashSet<NodeId>,
+    },
+    /// Delivery to any one member completes the message.
+    Anycast,
+}
+
+impl PendingDestinations {
+    pub fn new(destination: &MessageDestination<Shared<Node>>, sim: &IpnSim) -> Self {
+        match destination {
+            MessageDestination::<Shared<Node>>::Multicast(group_id) => PendingDestinations::Multicast {
+                group_id: *group_id,
+                delivered_ids: HashSet::new(),
+            },
+            MessageDestination::<Shared<Node>>::Anycast(_) => PendingDestinations::Anycast,
+            _ => PendingDestinations::Nodes(destination_to_ids(destination, sim)),
+        }
+    }
+
+    pub fn is_group(&self) -> bool {
+        !matches!(self, PendingDestinations::Nodes(_))
+    }
+
+    /// Records a delivery, returning whether the message has now reached every destination.
+    pub fn deliver(&mut self, node_id: NodeId, sim: &IpnSim) -> bool {
+        match self {
+            PendingDestinations::Nodes(remaining_ids) => {
+                remaining_ids.remove(&node_id);
+                remaining_ids.is_empty()
+            }
+            PendingDestinations::Multicast { group_id, delivered_ids } => {
+                delivered_ids.insert(node_id);
+                sim.groups
+                    .members(*group_id)
+                    .iter()
+                    .all(|member_id| delivered_ids.contains(member_id))
+            }
+            PendingDestinations::Anycast => true,
+        }
     }
 }
@@ -125,7 +125,7 @@ impl Component for MessageGraph {
 
                         let mut color = if Rc::ptr_eq(&message_data.source_node, node) {
                             "green"
-                        } else if message_data.destination.is_included(node) {
+                        } else if message_data.destination.is_included(node, &wrapper.sim.groups) {
                             "red"
                         } else {
                             "blue"
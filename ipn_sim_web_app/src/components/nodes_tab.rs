@@ -138,6 +138,20 @@ impl Component for NodesTab {
                     </h4>
                 </div>
             </div>
+            <div class="row">
+                <div class="col">
+                    <h2>
+                        { "Velocity:" }
+                    </h2>
+                </div>
+            </div>
+            <div class="row">
+                <div class="col">
+                    <h4 class="fw-normal">
+                        { utils::format_velocity(selected_node.velocity) }
+                    </h4>
+                </div>
+            </div>
             <div class="row">
                 <div class="col">
                     <h2>
@@ -2,7 +2,9 @@ use yew::prelude::*;
 
 use ipn_sim_lib::event::Event;
 use ipn_sim_lib::events::awake_router_event::AwakeRouterEvent;
+use ipn_sim_lib::events::ltp_timer_event::LtpTimerEvent;
 use ipn_sim_lib::events::create_message_event::CreateMessageEvent;
+use ipn_sim_lib::events::group_membership_event::{GroupMembershipEvent, MembershipChange};
 use ipn_sim_lib::events::receive_data_event::ReceiveDataEvent;
 use ipn_sim_lib::events::transmit_end_event::TransmitEndEvent;
 use ipn_sim_lib::events::transmit_start_event::TransmitStartEvent;
@@ -76,7 +78,7 @@ impl EventHtml for TransmitEndEvent {
     fn get_details(&self) -> Vec<Html> {
         vec![
             Html::from(&self.node.borrow().name),
-            html! {<Data data=self.data.clone()/>},
+            html! {<Data data=self.packet.data.clone()/>},
         ]
     }
 }
@@ -89,7 +91,7 @@ impl EventHtml for ReceiveDataEvent {
     fn get_details(&self) -> Vec<Html> {
         vec![
             Html::from(&self.node.borrow().name),
-            html! {<Data data=self.data.clone()/>},
+            html! {<Data data=self.packet.data.clone()/>},
         ]
     }
 }
@@ -103,3 +105,32 @@ impl EventHtml for AwakeRouterEvent {
         vec![Html::from(&self.node.borrow().name)]
     }
 }
+
+impl EventHtml for LtpTimerEvent {
+    fn get_title(&self) -> &'static str {
+        "LtpTimer"
+    }
+
+    fn get_details(&self) -> Vec<Html> {
+        vec![
+            Html::from(&self.node.borrow().name),
+            Html::from(format!("session {}", self.session_number)),
+        ]
+    }
+}
+
+impl EventHtml for GroupMembershipEvent {
+    fn get_title(&self) -> &'static str {
+        "GroupMembership"
+    }
+
+    fn get_details(&self) -> Vec<Html> {
+        vec![
+            Html::from(&self.node.borrow().name),
+            Html::from(match self.change {
+                MembershipChange::Join => format!("joined group {}", self.group_id),
+                MembershipChange::Leave => format!("left group {}", self.group_id),
+            }),
+        ]
+    }
+}
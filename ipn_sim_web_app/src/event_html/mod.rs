@@ -6,6 +6,8 @@ use event_html::EventHtml;
 use ipn_sim_lib::event::Event;
 use ipn_sim_lib::events::awake_router_event::AwakeRouterEvent;
 use ipn_sim_lib::events::create_message_event::CreateMessageEvent;
+use ipn_sim_lib::events::group_membership_event::GroupMembershipEvent;
+use ipn_sim_lib::events::ltp_timer_event::LtpTimerEvent;
 use ipn_sim_lib::events::receive_data_event::ReceiveDataEvent;
 use ipn_sim_lib::events::router_event::{RouterEvent, RouterEventType};
 use ipn_sim_lib::events::transmit_end_event::TransmitEndEvent;
@@ -22,6 +24,8 @@ pub fn event_to_html(event: &Box<dyn Event>) -> Html {
         .or_else(|| TransmitEndEvent::try_to_html(event))
         .or_else(|| ReceiveDataEvent::try_to_html(event))
         .or_else(|| AwakeRouterEvent::try_to_html(event))
+        .or_else(|| LtpTimerEvent::try_to_html(event))
+        .or_else(|| GroupMembershipEvent::try_to_html(event))
         .or_else(|| RouterEvent::try_to_html(event))
         .unwrap_or(html! {
         <div class="row border-bottom border-secondary">
@@ -12,8 +12,8 @@ use ipn_sim_lib::node::node_builder::NodeBuilder;
 use ipn_sim_lib::routers::{epidemic, epidemic_2};
 use ipn_sim_lib::routers::epidemic::epidemic::Epidemic;
 use ipn_sim_lib::routers::epidemic::flavours::{vanilla::Vanilla, ack::Ack as AckFlavour};
-use ipn_sim_lib::routers::source_spray_and_wait::source_spray_and_wait::SourceSprayAndWait;
-use ipn_sim_lib::routers::source_spray_and_wait_2::source_spray_and_wait_2::SourceSprayAndWait2;
+use ipn_sim_lib::routers::spray_and_wait::flavours::binary::Binary;
+use ipn_sim_lib::routers::spray_and_wait::spray_and_wait::SprayAndWait;
 use ipn_sim_lib::routers::test_router::TestRouter;
 use ipn_sim_lib::transceiver::transceive_guards::simple::SimpleTransceiveGuard;
 use ipn_sim_lib::transceiver::transceiver::Transceiver;
@@ -170,7 +170,7 @@ pub fn orbiting_rings() -> IpnSimBuilder {
                         true
                     ))
                     // .router(Epidemic::<Ack>::new(1024 * 8, 1_000_000_000 * 3600))
-                    // .router(SourceSprayAndWait2::new(1024 * 8))
+                    // .router(SprayAndWait::new(Binary, 1024 * 8, 50))
                     .transceive_speed(1.)
                     .transceive_guard(SimpleTransceiveGuard::new(
                         2.2 * max_ring_radius / ring_sizes.len() as SpaceMetric,
@@ -1,9 +1,15 @@
 use ipn_sim_lib::cgmath::Point3;
 use ipn_sim_lib::downcast_rs::*;
 use ipn_sim_lib::movement::Movement;
+use ipn_sim_lib::movements::composite_movement::CompositeMovement;
+use ipn_sim_lib::movements::ephemeris_movement::EphemerisMovement;
+use ipn_sim_lib::movements::free_flight_movement::FreeFlightMovement;
 use ipn_sim_lib::movements::orbital_movement::OrbitalMovement;
 use ipn_sim_lib::movements::path_movement::PathMovement;
+use ipn_sim_lib::movements::relative_movement::RelativeMovement;
 use ipn_sim_lib::movements::static_movement::StaticMovement;
+use ipn_sim_lib::movements::surface_movement::SurfaceMovement;
+use ipn_sim_lib::movements::tle_movement::TleMovement;
 use ipn_sim_lib::utils::{SpaceMetric, TimeMetric};
 
 pub trait MovementPath: Movement {
@@ -45,6 +51,25 @@ impl MovementPath for StaticMovement {
 
 impl MovementPath for PathMovement {}
 
+impl MovementPath for EphemerisMovement {}
+
+impl MovementPath for SurfaceMovement {}
+
+impl MovementPath for FreeFlightMovement {}
+
+impl MovementPath for CompositeMovement {}
+
+impl MovementPath for RelativeMovement {}
+
+impl MovementPath for TleMovement {
+    fn get_path(&self, interval: TimeMetric, length: TimeMetric) -> Vec<Point3<SpaceMetric>> {
+        (0..(self.period().min(length as f64)) as TimeMetric)
+            .step_by(interval as usize)
+            .map(|time| self.get_position_at(time))
+            .collect::<Vec<Point3<SpaceMetric>>>()
+    }
+}
+
 pub fn get_movement_path(
     movement: &Box<dyn Movement>,
     interval: TimeMetric,
@@ -53,5 +78,11 @@ pub fn get_movement_path(
     OrbitalMovement::try_get_path(movement, interval, length)
         .or_else(|| StaticMovement::try_get_path(movement, interval, length))
         .or_else(|| PathMovement::try_get_path(movement, interval, length))
+        .or_else(|| TleMovement::try_get_path(movement, interval, length))
+        .or_else(|| EphemerisMovement::try_get_path(movement, interval, length))
+        .or_else(|| SurfaceMovement::try_get_path(movement, interval, length))
+        .or_else(|| FreeFlightMovement::try_get_path(movement, interval, length))
+        .or_else(|| CompositeMovement::try_get_path(movement, interval, length))
+        .or_else(|| RelativeMovement::try_get_path(movement, interval, length))
         .expect("No movement path implementation!")
 }
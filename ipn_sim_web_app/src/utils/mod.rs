@@ -3,7 +3,7 @@ use std::rc::Rc;
 
 use yew::prelude::*;
 
-use ipn_sim_lib::cgmath::Point3;
+use ipn_sim_lib::cgmath::{InnerSpace, Point3, Vector3};
 use ipn_sim_lib::utils::{Shared, SpaceMetric};
 
 use crate::sim_wrapper::sim_wrapper::SimWrapper;
@@ -21,6 +21,16 @@ pub fn format_position(position: Point3<SpaceMetric>) -> String {
     )
 }
 
+pub fn format_velocity(velocity: Vector3<SpaceMetric>) -> String {
+    format!(
+        "({:.3}km/s, {:.3}km/s, {:.3}km/s), {:.3}km/s",
+        velocity.x / 1000.,
+        velocity.y / 1000.,
+        velocity.z / 1000.,
+        velocity.magnitude() / 1000.,
+    )
+}
+
 #[derive(Properties, Clone)]
 pub struct WrapperProps {
     pub wrapper: Shared<SimWrapper>,
@@ -35,5 +45,7 @@ pub fn message_destination_string(destination: &MessageDestination<Shared<Node>>
             .map(|node| node.borrow().name.clone())
             .collect::<Vec<_>>()
             .join(", "),
+        MessageDestination::<Shared<Node>>::Multicast(group_id) => format!("group {}", group_id),
+        MessageDestination::<Shared<Node>>::Anycast(group_id) => format!("any member of group {}", group_id),
     }
 }
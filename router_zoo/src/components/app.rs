@@ -39,13 +39,30 @@ impl Component for App {
             ("Home", NavItem::Link(Route::Home)),
             (
                 "Routers",
-                NavItem::SubMenu(vec![(
-                    "Epidemic",
-                    NavItem::SubMenu(vec![
-                        ("Vanilla", NavItem::Link(Route::Routers("epidemic".into()))),
-                        ("Acknowledged", NavItem::Link(Route::Routers("epidemic-ack".into()))),
-                    ]),
-                )]),
+                NavItem::SubMenu(vec![
+                    (
+                        "Baselines",
+                        NavItem::SubMenu(vec![
+                            ("Direct Delivery", NavItem::Link(Route::Routers("direct-delivery".into()))),
+                            ("First Contact", NavItem::Link(Route::Routers("first-contact".into()))),
+                        ]),
+                    ),
+                    (
+                        "Epidemic",
+                        NavItem::SubMenu(vec![
+                            ("Vanilla", NavItem::Link(Route::Routers("epidemic".into()))),
+                            ("Acknowledged", NavItem::Link(Route::Routers("epidemic-ack".into()))),
+                        ]),
+                    ),
+                    (
+                        "PRoPHET",
+                        NavItem::SubMenu(vec![
+                            ("GRTR", NavItem::Link(Route::Routers("prophet-grtr".into()))),
+                            ("GRTRMax", NavItem::Link(Route::Routers("prophet-grtr-max".into()))),
+                            ("COIN", NavItem::Link(Route::Routers("prophet-coin".into()))),
+                        ]),
+                    ),
+                ]),
             ),
         ]);
 
@@ -6,6 +6,10 @@ use crate::router_details::ack_epidemic::AckEpidemic;
 use ipn_sim_lib::router::Router;
 use ipn_sim_lib::routers::epidemic::epidemic::Epidemic;
 use ipn_sim_lib::routers::epidemic::flavours::{vanilla::Vanilla, ack::Ack};
+use ipn_sim_lib::routers::prophet::prophet::Prophet;
+use ipn_sim_lib::routers::direct_delivery::direct_delivery::DirectDelivery;
+use ipn_sim_lib::routers::first_contact::first_contact::FirstContact;
+use ipn_sim_lib::routers::prophet::strategies::{coin::Coin, grtr::Grtr, grtr_max::GrtrMax};
 
 pub struct RouterPage<R: Router + RouterDetails + Clone + 'static> {
     link: ComponentLink<Self>,
@@ -49,6 +53,21 @@ pub fn router_page_from_key(key: impl ToString) -> Html {
         "epidemic-ack" => html! {
                 <RouterPage<Epidemic<Ack>>/>
             },
+        "direct-delivery" => html! {
+                <RouterPage<DirectDelivery>/>
+            },
+        "first-contact" => html! {
+                <RouterPage<FirstContact>/>
+            },
+        "prophet-grtr" => html! {
+                <RouterPage<Prophet<Grtr>>/>
+            },
+        "prophet-grtr-max" => html! {
+                <RouterPage<Prophet<GrtrMax>>/>
+            },
+        "prophet-coin" => html! {
+                <RouterPage<Prophet<Coin>>/>
+            },
         _ => {
             unreachable!()
         }
@@ -26,6 +26,6 @@ impl RouterDetails for Epidemic<Ack> {
     }
 
     fn build_instance(string_params: Box<[String]>) -> Self {
-        Self::new(string_params[0].parse().unwrap(), 1_000_000_000 * 3600)
+        Self::new(string_params[0].parse::<usize>().unwrap(), 1_000_000_000 * 3600)
     }
 }
\ No newline at end of file
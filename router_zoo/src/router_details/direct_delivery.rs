@@ -0,0 +1,27 @@
+use crate::router_details::{RouterDetails, RouterParamType};
+use ipn_sim_lib::routers::direct_delivery::direct_delivery::DirectDelivery;
+
+impl RouterDetails for DirectDelivery {
+
+    fn name() -> &'static str {
+        "Direct Delivery"
+    }
+
+    fn params() -> Box<[(&'static str, RouterParamType)]> {
+        Box::new([
+            ("Vector Size", RouterParamType::Number),
+            ("Beacon Interval", RouterParamType::Number),
+        ])
+    }
+
+    fn default_params() -> Box<[String]> {
+        Box::new([
+            (1024 * 8).to_string(),
+            (1_000_000_000u64 * 60 * 15).to_string()
+        ])
+    }
+
+    fn build_instance(string_params: Box<[String]>) -> Self {
+        Self::new(string_params[0].parse::<usize>().unwrap(), string_params[1].parse().unwrap())
+    }
+}
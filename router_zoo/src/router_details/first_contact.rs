@@ -0,0 +1,27 @@
+use crate::router_details::{RouterDetails, RouterParamType};
+use ipn_sim_lib::routers::first_contact::first_contact::FirstContact;
+
+impl RouterDetails for FirstContact {
+
+    fn name() -> &'static str {
+        "First Contact"
+    }
+
+    fn params() -> Box<[(&'static str, RouterParamType)]> {
+        Box::new([
+            ("Vector Size", RouterParamType::Number),
+            ("Beacon Interval", RouterParamType::Number),
+        ])
+    }
+
+    fn default_params() -> Box<[String]> {
+        Box::new([
+            (1024 * 8).to_string(),
+            (1_000_000_000u64 * 60 * 15).to_string()
+        ])
+    }
+
+    fn build_instance(string_params: Box<[String]>) -> Self {
+        Self::new(string_params[0].parse::<usize>().unwrap(), string_params[1].parse().unwrap())
+    }
+}
@@ -2,6 +2,9 @@ use ipn_sim_lib::router::Router;
 
 pub mod vanilla_epidemic;
 pub mod ack_epidemic;
+pub mod prophet;
+pub mod direct_delivery;
+pub mod first_contact;
 
 
 pub trait RouterDetails {
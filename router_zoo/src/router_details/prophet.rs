@@ -0,0 +1,41 @@
+use crate::router_details::{RouterDetails, RouterParamType};
+use ipn_sim_lib::routers::prophet::predictabilities::PredictabilityParams;
+use ipn_sim_lib::routers::prophet::prophet::Prophet;
+use ipn_sim_lib::routers::prophet::strategies::{coin::Coin, grtr::Grtr, grtr_max::GrtrMax};
+
+macro_rules! impl_prophet_details {
+    ($strategy:ty, $name:expr) => {
+        impl RouterDetails for Prophet<$strategy> {
+
+            fn name() -> &'static str {
+                $name
+            }
+
+            fn params() -> Box<[(&'static str, RouterParamType)]> {
+                Box::new([
+                    ("Vector Size", RouterParamType::Number),
+                    ("Beacon Interval", RouterParamType::Number),
+                ])
+            }
+
+            fn default_params() -> Box<[String]> {
+                Box::new([
+                    (1024 * 8).to_string(),
+                    (1_000_000_000u64 * 60 * 15).to_string()
+                ])
+            }
+
+            fn build_instance(string_params: Box<[String]>) -> Self {
+                Self::new(
+                    string_params[0].parse::<usize>().unwrap(),
+                    string_params[1].parse().unwrap(),
+                    PredictabilityParams::default(),
+                )
+            }
+        }
+    };
+}
+
+impl_prophet_details!(Grtr, "PRoPHET (GRTR)");
+impl_prophet_details!(GrtrMax, "PRoPHET (GRTRMax)");
+impl_prophet_details!(Coin, "PRoPHET (COIN)");